@@ -0,0 +1,196 @@
+//! Runs placement scenarios from JSON or TOML files.
+//!
+//! ```text
+//! floater [--check] <scenario files...>
+//! ```
+//!
+//! Prints the resulting position of each scenario. With `--check`, the
+//! result is compared against the `expected` table of each file instead, and
+//! the process exits with an error if any do not match.
+
+mod scenario;
+
+use std::{fmt::Write, fs, path::Path, process::ExitCode};
+
+use floater::{
+    geometry::{Alignment, Side},
+    modifiers::{arrow::ArrowData, hide::HideData},
+    PositionInfo,
+};
+use scenario::{Expected, Scenario};
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut paths = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            "-h" | "--help" => {
+                println!("usage: floater [--check] <scenario files...>");
+                return ExitCode::SUCCESS;
+            }
+            _ => paths.push(arg),
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("usage: floater [--check] <scenario files...>");
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+    for path in &paths {
+        let result = load(Path::new(path)).and_then(|scenario| {
+            let info = run(&scenario);
+            if check {
+                check_expected(&scenario, &info)
+            } else {
+                Ok(format_info(&info))
+            }
+        });
+
+        match result {
+            Ok(output) if check => println!("ok: {path}{output}"),
+            Ok(output) => println!("{path}\n{output}"),
+            Err(err) => {
+                failed = true;
+                eprintln!("FAILED: {path}\n{err}");
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn load(path: &Path) -> Result<Scenario, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("could not read file: {e}"))?;
+    if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&text).map_err(|e| format!("invalid scenario: {e}"))
+    } else {
+        serde_json::from_str(&text).map_err(|e| format!("invalid scenario: {e}"))
+    }
+}
+
+fn run(scenario: &Scenario) -> PositionInfo {
+    scenario
+        .pipeline()
+        .compute(scenario.reference, scenario.floater, scenario.container)
+}
+
+fn format_info(info: &PositionInfo) -> String {
+    let rect = info.rect;
+    let mut out = format!(
+        "  rect: x = {}, y = {}, width = {}, height = {}\n  side: {}\n  align: {}",
+        rect.x(),
+        rect.y(),
+        rect.width(),
+        rect.height(),
+        side_name(info.side),
+        align_name(info.align),
+    );
+    if let Some(arrow) = info.data.get::<ArrowData>() {
+        _ = write!(
+            out,
+            "\n  arrow: offset = {}, center_offset = {}",
+            arrow.offset(),
+            arrow.center_offset()
+        );
+    }
+    if let Some(hide) = info.data.get::<HideData>() {
+        _ = write!(
+            out,
+            "\n  hide: reference_hidden = {}, escaped = {}",
+            hide.reference_hidden(),
+            hide.escaped()
+        );
+    }
+    out
+}
+
+/// Returns an error listing every value that does not match.
+fn check_expected(scenario: &Scenario, info: &PositionInfo) -> Result<String, String> {
+    let Some(expected) = &scenario.expected else {
+        return Err("no `expected` values to check against".to_string());
+    };
+    let Expected {
+        rect,
+        side,
+        align,
+        arrow_offset,
+        reference_hidden,
+        tolerance,
+    } = *expected;
+
+    let mut errors = Vec::new();
+    let mut compare = |name: &str, expected: f64, actual: f64| {
+        if (expected - actual).abs() > tolerance {
+            errors.push(format!("  {name}: expected {expected}, got {actual}"));
+        }
+    };
+
+    if let Some(rect) = rect {
+        compare("rect.x", rect.x(), info.rect.x());
+        compare("rect.y", rect.y(), info.rect.y());
+        compare("rect.width", rect.width(), info.rect.width());
+        compare("rect.height", rect.height(), info.rect.height());
+    }
+    if let Some(offset) = arrow_offset {
+        match info.data.get::<ArrowData>() {
+            Some(arrow) => compare("arrow_offset", offset, arrow.offset()),
+            None => errors.push("  arrow_offset: no arrow data was reported".to_string()),
+        }
+    }
+    if let Some(side) = side {
+        if side != info.side {
+            errors.push(format!(
+                "  side: expected {}, got {}",
+                side_name(side),
+                side_name(info.side)
+            ));
+        }
+    }
+    if let Some(align) = align {
+        if align != info.align {
+            errors.push(format!(
+                "  align: expected {}, got {}",
+                align_name(align),
+                align_name(info.align)
+            ));
+        }
+    }
+    if let Some(hidden) = reference_hidden {
+        let actual = info.data.get::<HideData>().map(HideData::reference_hidden);
+        if actual != Some(hidden) {
+            errors.push(format!(
+                "  reference_hidden: expected {hidden}, got {actual:?}"
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(String::new())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+const fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Left => "left",
+        Side::Top => "top",
+        Side::Right => "right",
+        Side::Bottom => "bottom",
+    }
+}
+
+const fn align_name(align: Alignment) -> &'static str {
+    match align {
+        Alignment::Start => "start",
+        Alignment::Center => "center",
+        Alignment::End => "end",
+    }
+}
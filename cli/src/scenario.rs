@@ -0,0 +1,58 @@
+use floater::{
+    config::{ModifierConfig, PipelineConfig},
+    geometry::{Alignment, ElemRect, ElemSize, Side},
+    Pipeline, DEFAULT_MAX_RESETS,
+};
+use serde::Deserialize;
+
+/// A placement scenario, read from a JSON or TOML file.
+///
+/// The pipeline fields are the same as [`PipelineConfig`]. They are not
+/// flattened, as that would allow unknown fields.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub reference: ElemRect,
+    pub floater: ElemSize,
+    pub container: ElemRect,
+    #[serde(default)]
+    pub side: Side,
+    #[serde(default)]
+    pub align: Alignment,
+    #[serde(default = "default_max_resets")]
+    pub max_resets: usize,
+    #[serde(default)]
+    pub modifiers: Vec<ModifierConfig>,
+    pub expected: Option<Expected>,
+}
+
+impl Scenario {
+    pub fn pipeline(&self) -> Pipeline {
+        PipelineConfig {
+            side: self.side,
+            align: self.align,
+            max_resets: self.max_resets,
+            modifiers: self.modifiers.clone(),
+        }
+        .pipeline()
+    }
+}
+
+const fn default_max_resets() -> usize { DEFAULT_MAX_RESETS }
+
+/// The output to compare against in `--check` mode. Values that are left out
+/// are not checked.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub rect: Option<ElemRect>,
+    pub side: Option<Side>,
+    pub align: Option<Alignment>,
+    pub arrow_offset: Option<f64>,
+    pub reference_hidden: Option<bool>,
+    /// The largest allowed difference between numbers.
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f64,
+}
+
+const fn default_tolerance() -> f64 { 1e-6 }
@@ -0,0 +1,83 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use floater::{
+    compute_position,
+    geometry::{Alignment, ElemRect, ElemSize, Placement, Side},
+    modifiers::{arrow, auto_placement, avoid, flip, hide, offset, resize, round, shift},
+    padding::Padding,
+    try_compute_position, PositionOpts,
+};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    reference: [f64; 4],
+    floater: [f64; 2],
+    container: [f64; 4],
+    side: u8,
+    align: u8,
+    padding: [f64; 3],
+    arrow: [f64; 2],
+    scale: f64,
+}
+
+fn rect([x, y, w, h]: [f64; 4]) -> ElemRect { ElemRect::new(x, y, w, h) }
+
+fuzz_target!(|input: Input| {
+    let side = match input.side % 4 {
+        0 => Side::Left,
+        1 => Side::Top,
+        2 => Side::Right,
+        _ => Side::Bottom,
+    };
+    let align = match input.align % 3 {
+        0 => Alignment::Start,
+        1 => Alignment::Center,
+        _ => Alignment::End,
+    };
+    let [outward, inward, cross] = input.padding;
+    let padding = Padding {
+        outward,
+        inward,
+        cross,
+    };
+    let reference = rect(input.reference);
+    let floater = ElemSize::new(input.floater[0], input.floater[1]);
+    let container = rect(input.container);
+
+    let placement = Placement::new(side, align);
+
+    // must never panic, whatever the input
+    let _ = compute_position(
+        reference,
+        floater,
+        container,
+        PositionOpts::new()
+            .with_placement(placement)
+            .add_modifier(&mut offset(outward))
+            .add_modifier(&mut auto_placement().padding(padding))
+            .add_modifier(&mut flip().padding(padding).flip_cross(true))
+            .add_modifier(&mut resize(|available, _| *available).padding(padding))
+            .add_modifier(&mut shift().padding(padding).main_axis(true))
+            .add_modifier(&mut avoid([reference]))
+            .add_modifier(&mut arrow(input.arrow[0]).padding(input.arrow[1]))
+            .add_modifier(&mut hide())
+            .add_modifier(&mut round(input.scale)),
+    );
+
+    // validated input must produce a finite position
+    if let Ok(info) = try_compute_position(
+        reference,
+        floater,
+        container,
+        PositionOpts::new()
+            .with_placement(placement)
+            .add_modifier(&mut flip().padding(padding))
+            .add_modifier(&mut shift().padding(padding)),
+    ) {
+        if padding.outward.is_finite() && padding.inward.is_finite() && padding.cross.is_finite() {
+            assert!(info.rect.is_finite());
+        }
+    }
+});
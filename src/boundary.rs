@@ -0,0 +1,99 @@
+use crate::geometry::ElemRect;
+
+/// The area that the floater should be kept inside of.
+///
+/// This is made of a clipping boundary, which is the intersection of every
+/// rect that can clip the floater (e.g. each scrolling ancestor), and an
+/// optional root boundary (e.g. the viewport).
+///
+/// All rects must be relative to the same scrolling context as the reference.
+#[derive(Debug, Clone, Copy)]
+pub struct Boundary {
+    clipping: ElemRect,
+    root: Option<ElemRect>,
+}
+
+impl Boundary {
+    #[must_use]
+    pub const fn new(clipping: ElemRect) -> Self {
+        Self {
+            clipping,
+            root: None,
+        }
+    }
+
+    /// Creates a clipping boundary from the intersection of all the rects.
+    ///
+    /// Returns [`None`] if `rects` is empty.
+    #[must_use]
+    pub fn from_rects(rects: impl IntoIterator<Item = ElemRect>) -> Option<Self> {
+        rects
+            .into_iter()
+            .reduce(|acc, rect| acc.intersect(&rect))
+            .map(Self::new)
+    }
+
+    /// Adds another rect to the clipping boundary.
+    #[must_use]
+    pub fn clip(mut self, rect: ElemRect) -> Self {
+        self.clipping = self.clipping.intersect(&rect);
+        self
+    }
+
+    /// Sets the root boundary, which usually represents the viewport.
+    #[must_use]
+    pub const fn with_root(mut self, root: ElemRect) -> Self {
+        self.root = Some(root);
+        self
+    }
+
+    #[must_use]
+    pub const fn clipping(&self) -> ElemRect { self.clipping }
+
+    #[must_use]
+    pub const fn root(&self) -> Option<ElemRect> { self.root }
+
+    /// Returns the rect to check against for the given kind of boundary.
+    ///
+    /// If there is no root boundary, [`BoundaryKind::Root`] uses the clipping
+    /// boundary instead.
+    #[must_use]
+    pub fn rect(&self, kind: BoundaryKind) -> ElemRect {
+        match (kind, self.root) {
+            (BoundaryKind::Both, Some(root)) => self.clipping.intersect(&root),
+            (BoundaryKind::Root, Some(root)) => root,
+            (BoundaryKind::Both | BoundaryKind::Clipping | BoundaryKind::Root, _) => self.clipping,
+        }
+    }
+}
+
+impl From<ElemRect> for Boundary {
+    fn from(value: ElemRect) -> Self { Self::new(value) }
+}
+
+/// Which part of a [`Boundary`] a modifier should check against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum BoundaryKind {
+    /// The intersection of the clipping and root boundaries.
+    #[default]
+    Both,
+    /// Only the clipping boundary.
+    Clipping,
+    /// Only the root boundary.
+    Root,
+}
+
+#[macro_export]
+macro_rules! impl_boundary_builder {
+    ($path:ident) => {
+        /// Which part of the [`Boundary`](crate::boundary::Boundary) to keep
+        /// the floater inside of.
+        #[must_use]
+        pub const fn boundary(mut self, boundary: $crate::boundary::BoundaryKind) -> Self {
+            self.$path = boundary;
+            self
+        }
+    };
+}
@@ -0,0 +1,341 @@
+//! Data-only descriptions of a [`Pipeline`], which can be loaded from a file
+//! with any serde format.
+//!
+//! Options that are left out use the modifier's defaults.
+//!
+//! ```
+//! # use floater::{config::PipelineConfig, geometry::*};
+//! let config: PipelineConfig = serde_json::from_str(
+//!     r#"{
+//!         "side": "top",
+//!         "modifiers": [
+//!             { "type": "flip", "padding": 5 },
+//!             { "type": "shift", "padding": 5, "limiter": { "attached": 10 } },
+//!             { "type": "offset", "main_axis": 4 },
+//!             { "type": "arrow", "len": 10 }
+//!         ]
+//!     }"#,
+//! )
+//! .unwrap();
+//!
+//! let info = config.pipeline().compute(
+//!     ElemRect::new(40.0, 10.0, 20.0, 20.0),
+//!     ElemSize::new(50.0, 30.0),
+//!     ElemRect::new(0.0, 0.0, 200.0, 100.0),
+//! );
+//! assert_eq!(info.side, Side::Bottom);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    boundary::BoundaryKind,
+    geometry::{Alignment, ElemRect, ElemSize, Placement, Side},
+    modifiers::{
+        arrow,
+        auto_placement::{auto_placement, AutoPlacement},
+        flip::{flip, FallbackAxisSideDirection, FallbackMethod, Flip},
+        hide::{hide, Hide},
+        offset::{offset, Offsets},
+        resize::{resize, Resize},
+        round,
+        shift::{limiter, shift, Shift},
+        ModifierState,
+    },
+    padding::Padding,
+    Pipeline, DEFAULT_MAX_RESETS,
+};
+
+/// A description of a [`Pipeline`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PipelineConfig {
+    pub side: Side,
+    pub align: Alignment,
+    /// See [`PositionOpts::with_max_resets`](crate::PositionOpts::with_max_resets).
+    pub max_resets: usize,
+    /// The modifiers to run, in order.
+    pub modifiers: Vec<ModifierConfig>,
+}
+
+impl PipelineConfig {
+    /// Builds the pipeline. Any [`resize`](ModifierConfig::Resize) modifiers
+    /// use [`fit_available`] to calculate the new size.
+    #[must_use]
+    pub fn pipeline(&self) -> Pipeline { self.pipeline_with_resize(fit_available) }
+
+    /// Builds the pipeline, using `recalculator` for any
+    /// [`resize`](ModifierConfig::Resize) modifiers. See
+    /// [`resize`](crate::modifiers::resize()) for what this function should do.
+    #[must_use]
+    pub fn pipeline_with_resize<F>(&self, recalculator: F) -> Pipeline
+    where
+        F: FnMut(&ElemSize, &ModifierState) -> ElemSize + Clone + Send + Sync + 'static,
+    {
+        self.modifiers.iter().fold(
+            Pipeline::new()
+                .with_placement(Placement::new(self.side, self.align))
+                .with_max_resets(self.max_resets),
+            |pipeline, modifier| modifier.add_to(pipeline, &recalculator),
+        )
+    }
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            side: Side::default(),
+            align: Alignment::default(),
+            max_resets: DEFAULT_MAX_RESETS,
+            modifiers: Vec::new(),
+        }
+    }
+}
+
+/// Shrinks the floater to fit in the available space, like the CSS
+/// `max-width` and `max-height` properties. The floater never grows.
+#[must_use]
+pub const fn fit_available(available: &ElemSize, state: &ModifierState) -> ElemSize {
+    let size = state.floater.size();
+    ElemSize::new(
+        size.width().min(available.width()).max(0.0),
+        size.height().min(available.height()).max(0.0),
+    )
+}
+
+/// A modifier and its options.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModifierConfig {
+    Offset(Offsets),
+    Flip(FlipConfig),
+    AutoPlacement(AutoPlacementConfig),
+    Shift(ShiftConfig),
+    Arrow(ArrowConfig),
+    Resize(ResizeConfig),
+    Hide(HideConfig),
+    Round(RoundConfig),
+}
+
+impl ModifierConfig {
+    fn add_to<F>(&self, pipeline: Pipeline, recalculator: &F) -> Pipeline
+    where
+        F: FnMut(&ElemSize, &ModifierState) -> ElemSize + Clone + Send + Sync + 'static,
+    {
+        match self {
+            Self::Offset(offsets) => pipeline.add_modifier(offset(*offsets)),
+            Self::Flip(config) => pipeline.add_modifier(config.modifier()),
+            Self::AutoPlacement(config) => pipeline.add_modifier(config.modifier()),
+            Self::Shift(config) => config.add_to(pipeline),
+            Self::Arrow(config) => pipeline.add_modifier(arrow(config.len).padding(config.padding)),
+            Self::Resize(config) => pipeline.add_modifier(config.modifier(recalculator.clone())),
+            Self::Hide(config) => pipeline.add_modifier(config.modifier()),
+            Self::Round(config) => pipeline.add_modifier(round(config.scale)),
+        }
+    }
+}
+
+/// Options for [`flip`]. See the builder methods on [`Flip`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FlipConfig {
+    pub padding: Option<Padding>,
+    pub boundary: Option<BoundaryKind>,
+    pub flip_main: Option<bool>,
+    pub flip_cross: Option<bool>,
+    pub check_main_axis: Option<bool>,
+    pub check_cross_axis: Option<bool>,
+    pub fallback_method: Option<FallbackMethod>,
+    pub fallback_placements: Option<Vec<Placement>>,
+    pub fallback_axis_side_direction: Option<FallbackAxisSideDirection>,
+    pub flip_alignment: Option<bool>,
+    pub min_size: Option<ElemSize>,
+    pub hysteresis: Option<f64>,
+}
+
+impl FlipConfig {
+    /// Creates the modifier.
+    #[must_use]
+    pub fn modifier(&self) -> Flip {
+        let mut m = flip();
+        m = apply(m, self.padding, Flip::padding);
+        m = apply(m, self.boundary, Flip::boundary);
+        m = apply(m, self.flip_main, Flip::flip_main);
+        m = apply(m, self.flip_cross, Flip::flip_cross);
+        m = apply(m, self.check_main_axis, Flip::check_main_axis);
+        m = apply(m, self.check_cross_axis, Flip::check_cross_axis);
+        m = apply(m, self.fallback_method, Flip::fallback_method);
+        m = apply(
+            m,
+            self.fallback_placements.clone(),
+            Flip::fallback_placements,
+        );
+        m = apply(
+            m,
+            self.fallback_axis_side_direction,
+            Flip::fallback_axis_side_direction,
+        );
+        m = apply(m, self.flip_alignment, Flip::flip_alignment);
+        m = apply(m, self.min_size, Flip::min_size);
+        apply(m, self.hysteresis, Flip::hysteresis)
+    }
+}
+
+/// Options for [`auto_placement`]. See the builder methods on
+/// [`AutoPlacement`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AutoPlacementConfig {
+    pub padding: Option<Padding>,
+    pub boundary: Option<BoundaryKind>,
+    pub allowed_sides: Option<Vec<Side>>,
+    pub check_cross_axis: Option<bool>,
+    pub alignment: Option<Alignment>,
+    pub auto_alignment: Option<bool>,
+}
+
+impl AutoPlacementConfig {
+    /// Creates the modifier.
+    #[must_use]
+    pub fn modifier(&self) -> AutoPlacement {
+        let mut m = auto_placement();
+        m = apply(m, self.padding, AutoPlacement::padding);
+        m = apply(m, self.boundary, AutoPlacement::boundary);
+        m = apply(m, self.allowed_sides.clone(), AutoPlacement::allowed_sides);
+        m = apply(m, self.check_cross_axis, AutoPlacement::check_cross_axis);
+        m = apply(m, self.alignment, AutoPlacement::alignment);
+        apply(m, self.auto_alignment, AutoPlacement::auto_alignment)
+    }
+}
+
+/// Options for [`shift`]. See the builder methods on [`Shift`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShiftConfig {
+    pub padding: Option<Padding>,
+    pub boundary: Option<BoundaryKind>,
+    pub main_axis: Option<bool>,
+    pub cross_axis: Option<bool>,
+    pub hysteresis: Option<f64>,
+    pub limiter: Option<LimiterConfig>,
+}
+
+impl ShiftConfig {
+    fn add_to(&self, pipeline: Pipeline) -> Pipeline {
+        let mut m = shift();
+        m = apply(m, self.padding, Shift::padding);
+        m = apply(m, self.boundary, Shift::boundary);
+        m = apply(m, self.main_axis, Shift::main_axis);
+        m = apply(m, self.cross_axis, Shift::cross_axis);
+        m = apply(m, self.hysteresis, Shift::hysteresis);
+
+        // each limiter is a different type, so the modifier can't be returned
+        match self.limiter {
+            None => pipeline.add_modifier(m),
+            Some(LimiterConfig::NoLimit) => pipeline.add_modifier(m.limiter(limiter::no_limit())),
+            Some(LimiterConfig::Attached(padding)) => {
+                pipeline.add_modifier(m.limiter(limiter::attached(padding)))
+            }
+            Some(LimiterConfig::AwayFromReference) => {
+                pipeline.add_modifier(m.limiter(limiter::away_from_reference()))
+            }
+            Some(LimiterConfig::LimitShift(config)) => {
+                let mut l = limiter::limit_shift();
+                l = apply(l, config.offset, limiter::LimitShift::offset);
+                l = apply(l, config.main_axis, limiter::LimitShift::main_axis);
+                l = apply(l, config.cross_axis, limiter::LimitShift::cross_axis);
+                pipeline.add_modifier(m.limiter(l))
+            }
+            Some(LimiterConfig::Within(rect)) => {
+                pipeline.add_modifier(m.limiter(limiter::within(rect)))
+            }
+            Some(LimiterConfig::ArrowRoom(config)) => {
+                pipeline.add_modifier(m.limiter(limiter::arrow_room(config.len, config.padding)))
+            }
+        }
+    }
+}
+
+/// One of the limiters in [`limiter`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimiterConfig {
+    NoLimit,
+    Attached(f64),
+    AwayFromReference,
+    LimitShift(LimitShiftConfig),
+    Within(ElemRect),
+    ArrowRoom(ArrowConfig),
+}
+
+/// Options for [`limit_shift`](limiter::limit_shift()).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitShiftConfig {
+    pub offset: Option<f64>,
+    pub main_axis: Option<bool>,
+    pub cross_axis: Option<bool>,
+}
+
+/// Options for [`arrow`](crate::modifiers::arrow()), also used by
+/// [`arrow_room`](limiter::arrow_room()).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArrowConfig {
+    pub len: f64,
+    #[serde(default)]
+    pub padding: f64,
+}
+
+/// Options for [`resize`](crate::modifiers::resize()). The function that
+/// calculates the new size is passed to
+/// [`PipelineConfig::pipeline_with_resize`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResizeConfig {
+    pub padding: Option<Padding>,
+    pub boundary: Option<BoundaryKind>,
+    pub reset: Option<bool>,
+}
+
+impl ResizeConfig {
+    /// Creates the modifier.
+    #[must_use]
+    pub fn modifier<F>(&self, recalculator: F) -> Resize<F>
+    where
+        F: FnMut(&ElemSize, &ModifierState) -> ElemSize,
+    {
+        let mut m = resize(recalculator);
+        m = apply(m, self.padding, Resize::padding);
+        m = apply(m, self.boundary, Resize::boundary);
+        apply(m, self.reset, Resize::reset)
+    }
+}
+
+/// Options for [`hide`](crate::modifiers::hide()).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HideConfig {
+    pub boundary: Option<BoundaryKind>,
+}
+
+impl HideConfig {
+    /// Creates the modifier.
+    #[must_use]
+    pub fn modifier(&self) -> Hide { apply(hide(), self.boundary, Hide::boundary) }
+}
+
+/// Options for [`round`](crate::modifiers::round()).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoundConfig {
+    pub scale: f64,
+}
+
+/// Applies a builder method if the option is set.
+fn apply<M, T>(modifier: M, value: Option<T>, f: impl FnOnce(M, T) -> M) -> M {
+    match value {
+        Some(value) => f(modifier, value),
+        None => modifier,
+    }
+}
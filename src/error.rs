@@ -0,0 +1,53 @@
+use core::fmt;
+
+/// Which input to [`try_compute_position`](crate::try_compute_position) was
+/// invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Reference,
+    Floater,
+    Container,
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Reference => "reference",
+            Self::Floater => "floater",
+            Self::Container => "container",
+        })
+    }
+}
+
+/// The reason the inputs to
+/// [`try_compute_position`](crate::try_compute_position) could not be
+/// positioned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PositionError {
+    /// A position or size is NaN or infinite.
+    NonFinite(Input),
+    /// A width or height is negative.
+    NegativeSize(Input),
+    /// The container has no area, e.g. if the clipping and root boundaries do
+    /// not overlap or one of them has a negative size.
+    EmptyContainer,
+    /// The floater is wider or taller than the container, so it can never fit.
+    FloaterTooLarge,
+    /// The reference is entirely outside of the container.
+    ReferenceOutside,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite(input) => write!(f, "{input} has a non-finite position or size"),
+            Self::NegativeSize(input) => write!(f, "{input} has a negative size"),
+            Self::EmptyContainer => f.write_str("container is empty"),
+            Self::FloaterTooLarge => f.write_str("floater is larger than the container"),
+            Self::ReferenceOutside => f.write_str("reference is outside of the container"),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
@@ -8,3 +8,7 @@ pub mod rect;
 pub use rect::ElemRect;
 pub mod size;
 pub use size::ElemSize;
+pub mod placement;
+pub use placement::{Alignment, Placement};
+pub mod point_ref;
+pub use point_ref::PointRef;
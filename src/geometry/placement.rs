@@ -0,0 +1,77 @@
+use super::{side::Axis, Side};
+
+/// How the floater lines up with the reference along the side it is placed
+/// on.
+///
+/// `Start` lines up the left/top edges, `End` lines up the right/bottom edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Alignment {
+    Start,
+    #[default]
+    Center,
+    End,
+}
+
+impl Alignment {
+    /// Returns the opposite alignment. [`Alignment::Center`] stays the same.
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Start => Self::End,
+            Self::Center => Self::Center,
+            Self::End => Self::Start,
+        }
+    }
+}
+
+/// Where to place the floater: a [`Side`] of the reference, and how to align
+/// the floater along that side.
+///
+/// A [`Side`] can be converted into a centered placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct Placement {
+    pub side: Side,
+    pub align: Alignment,
+}
+
+impl Placement {
+    #[must_use]
+    pub const fn new(side: Side, align: Alignment) -> Self { Self { side, align } }
+
+    #[must_use]
+    pub const fn with_side(mut self, side: Side) -> Self {
+        self.side = side;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_align(mut self, align: Alignment) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Returns the side of the floater that will be flush with the reference
+    /// due to the alignment.
+    ///
+    /// Returns [`None`] for [`Alignment::Center`].
+    #[must_use]
+    pub const fn aligned_side(self) -> Option<Side> {
+        let (start, end) = match self.side.axis() {
+            Axis::Vertical => (Side::Left, Side::Right),
+            Axis::Horizontal => (Side::Top, Side::Bottom),
+        };
+        match self.align {
+            Alignment::Start => Some(start),
+            Alignment::Center => None,
+            Alignment::End => Some(end),
+        }
+    }
+}
+
+impl From<Side> for Placement {
+    fn from(side: Side) -> Self { Self::new(side, Alignment::Center) }
+}
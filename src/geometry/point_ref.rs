@@ -0,0 +1,54 @@
+use super::{side::Axis, ElemRect, Vec2};
+
+/// A reference that follows a point instead of an element, such as the
+/// position of the mouse for a context menu or a cursor-following tooltip.
+///
+/// To lock the floater to the point where the user first clicked, keep using
+/// that initial point instead of updating it.
+///
+/// Use [`PointRef::rect`] to get the reference to pass to
+/// [`compute_position`](crate::compute_position).
+#[derive(Debug, Clone, Copy)]
+pub struct PointRef {
+    point: Vec2,
+    axis: Option<Axis>,
+}
+
+impl PointRef {
+    #[must_use]
+    pub const fn new(point: Vec2) -> Self { Self { point, axis: None } }
+
+    #[must_use]
+    pub const fn new_xy(x: f64, y: f64) -> Self { Self::new(Vec2::new(x, y)) }
+
+    /// Only follow the point along one axis. The real reference element's
+    /// position is used for the other axis.
+    ///
+    /// [`Axis::Horizontal`] follows the `x` coordinate of the point, while
+    /// [`Axis::Vertical`] follows the `y` coordinate.
+    ///
+    /// The real reference must be passed in to [`PointRef::rect`] for this to
+    /// have an effect.
+    #[must_use]
+    pub const fn follow_axis(mut self, axis: Axis) -> Self {
+        self.axis = Some(axis);
+        self
+    }
+
+    #[must_use]
+    pub const fn point(&self) -> Vec2 { self.point }
+
+    /// Returns the rect to use as the reference, given the real reference
+    /// element's rect.
+    ///
+    /// The rect is zero-sized along every axis that follows the point.
+    #[must_use]
+    pub const fn rect(&self, reference: ElemRect) -> ElemRect {
+        let Vec2 { x, y } = self.point;
+        match self.axis {
+            None => ElemRect::from_point(self.point),
+            Some(Axis::Horizontal) => ElemRect::new(x, reference.y(), 0.0, reference.height()),
+            Some(Axis::Vertical) => ElemRect::new(reference.x(), y, reference.width(), 0.0),
+        }
+    }
+}
@@ -7,6 +7,8 @@ use super::{size::ElemSize, Vec2};
 /// Positive `x` goes right, positive `y` goes down. Width and height must be
 /// non-negative.
 #[derive(Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "RectRepr", into = "RectRepr"))]
 pub struct ElemRect {
     point: Vec2,
     size: ElemSize,
@@ -24,6 +26,12 @@ impl ElemRect {
     #[must_use]
     pub const fn from_parts(point: Vec2, size: ElemSize) -> Self { Self { point, size } }
 
+    /// Creates a zero-sized rect at the point.
+    #[must_use]
+    pub const fn from_point(point: Vec2) -> Self {
+        Self::from_parts(point, ElemSize::new(0.0, 0.0))
+    }
+
     #[must_use]
     pub const fn x(&self) -> f64 { self.point.x }
     #[must_use]
@@ -33,6 +41,10 @@ impl ElemRect {
     #[must_use]
     pub const fn height(&self) -> f64 { self.size.height() }
 
+    /// Whether the position and size are neither NaN nor infinite.
+    #[must_use]
+    pub const fn is_finite(&self) -> bool { self.point.is_finite() && self.size.is_finite() }
+
     #[must_use]
     pub const fn left(&self) -> f64 { self.x() }
     #[must_use]
@@ -74,6 +86,51 @@ impl ElemRect {
 
         Self::new(left, top, right - left, bottom - top)
     }
+
+    /// Returns the smallest rect that contains both rects.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let left = self.x().min(other.x());
+        let top = self.y().min(other.y());
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+
+        Self::new(left, top, right - left, bottom - top)
+    }
+
+    /// Whether the two rects overlap. Rects that only touch at their edges
+    /// do not overlap.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.left() < other.right()
+            && other.left() < self.right()
+            && self.top() < other.bottom()
+            && other.top() < self.bottom()
+    }
+
+    /// Returns the rect grown by `amount` on every side.
+    #[must_use]
+    pub fn expand(&self, amount: f64) -> Self {
+        Self::new(
+            self.x() - amount,
+            self.y() - amount,
+            amount.mul_add(2.0, self.width()),
+            amount.mul_add(2.0, self.height()),
+        )
+    }
+
+    /// Returns the distance from the point to the closest edge of the rect, or
+    /// 0 if the point is inside the rect.
+    #[must_use]
+    pub fn distance_to(&self, point: Vec2) -> f64 {
+        let dx = (self.left() - point.x).max(point.x - self.right()).max(0.0);
+        let dy = (self.top() - point.y).max(point.y - self.bottom()).max(0.0);
+        dx.hypot(dy)
+    }
+}
+
+impl From<Vec2> for ElemRect {
+    fn from(value: Vec2) -> Self { Self::from_point(value) }
 }
 
 impl fmt::Debug for ElemRect {
@@ -86,3 +143,31 @@ impl fmt::Debug for ElemRect {
             .finish()
     }
 }
+
+/// The serialized form of an [`ElemRect`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RectRepr {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+#[cfg(feature = "serde")]
+impl From<RectRepr> for ElemRect {
+    fn from(r: RectRepr) -> Self { Self::new(r.x, r.y, r.width, r.height) }
+}
+
+#[cfg(feature = "serde")]
+impl From<ElemRect> for RectRepr {
+    fn from(rect: ElemRect) -> Self {
+        Self {
+            x: rect.x(),
+            y: rect.y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
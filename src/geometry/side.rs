@@ -2,6 +2,8 @@
 ///
 /// Also used as a direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Side {
     Left,
     Top,
@@ -79,6 +81,7 @@ impl Side {
 /// The direction a [`Side`] points toward, either horizontal or vertical.
 ///
 /// Created by [`Side::axis`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Axis {
     Vertical,
     Horizontal,
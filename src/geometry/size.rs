@@ -5,6 +5,8 @@ use super::{Side, Vec2};
 /// Note that the width and height values may be negative, e.g. to represent
 /// overlapping spaces.
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SizeRepr", into = "SizeRepr"))]
 pub struct ElemSize(Vec2);
 
 impl ElemSize {
@@ -22,6 +24,14 @@ impl ElemSize {
     #[must_use]
     pub const fn as_vec2(&self) -> &Vec2 { &self.0 }
 
+    /// Whether the width and height are neither NaN nor infinite.
+    #[must_use]
+    pub const fn is_finite(&self) -> bool { self.0.is_finite() }
+
+    /// Whether the width or height is negative.
+    #[must_use]
+    pub fn is_negative(&self) -> bool { self.width() < 0.0 || self.height() < 0.0 }
+
     /// Returns the length of the rectangle parallel to the provided side.
     #[must_use]
     pub const fn dim_cross(&self, side: Side) -> f64 { self.as_vec2().coord_cross(side) }
@@ -30,3 +40,27 @@ impl ElemSize {
     #[must_use]
     pub const fn dim_main(&self, side: Side) -> f64 { self.as_vec2().coord_main(side) }
 }
+
+/// The serialized form of an [`ElemSize`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SizeRepr {
+    width: f64,
+    height: f64,
+}
+
+#[cfg(feature = "serde")]
+impl From<SizeRepr> for ElemSize {
+    fn from(s: SizeRepr) -> Self { Self::new(s.width, s.height) }
+}
+
+#[cfg(feature = "serde")]
+impl From<ElemSize> for SizeRepr {
+    fn from(size: ElemSize) -> Self {
+        Self {
+            width: size.width(),
+            height: size.height(),
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::ops;
 use super::{side::Axis, Side};
 
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2 {
     pub x: f64,
     pub y: f64,
@@ -12,6 +13,10 @@ impl Vec2 {
     #[must_use]
     pub const fn new(x: f64, y: f64) -> Self { Self { x, y } }
 
+    /// Whether both coordinates are neither NaN nor infinite.
+    #[must_use]
+    pub const fn is_finite(&self) -> bool { self.x.is_finite() && self.y.is_finite() }
+
     /// Returns the component of the coordinate that is in the direction
     /// parallel to the provided side.
     ///
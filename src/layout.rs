@@ -0,0 +1,264 @@
+use crate::{
+    boundary::{Boundary, BoundaryKind},
+    compute_position,
+    geometry::{Alignment, ElemRect, ElemSize, Placement, Side},
+    modifiers::{
+        arrow::ArrowData,
+        flip::{flip, Flip},
+    },
+    Pipeline, PositionInfo,
+};
+
+/// Positions many floaters at once, so that they do not overlap each other.
+///
+/// Each floater is positioned with its own [`Pipeline`], so modifiers like
+/// [`flip`](crate::modifiers::flip) and [`shift`](crate::modifiers::shift)
+/// still apply. Floaters are placed in order of priority. If a floater
+/// overlaps one that has already been placed, its pipeline is run again with
+/// the other placements that its [`Flip`] would check, each with every
+/// alignment, until one does not overlap. If none of them fit, the floater is
+/// shifted along the side of the reference instead, as long as it stays
+/// next to the center of the reference.
+///
+/// Only the run with the chosen placement updates the state of stateful
+/// modifiers in the pipeline.
+///
+/// This is intended for things like chart annotations or map labels.
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    entries: Vec<LayoutEntry>,
+    gap: f64,
+    avoid_references: bool,
+}
+
+impl Layout {
+    #[must_use]
+    pub fn new() -> Self { Self::default() }
+
+    #[must_use]
+    pub fn add_entry(mut self, entry: LayoutEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// The minimum distance to keep between floaters.
+    #[must_use]
+    pub const fn gap(mut self, gap: f64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Whether floaters should also avoid covering the references of other
+    /// entries.
+    #[must_use]
+    pub const fn avoid_references(mut self, b: bool) -> Self {
+        self.avoid_references = b;
+        self
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[LayoutEntry] { &self.entries }
+
+    pub fn entries_mut(&mut self) -> &mut [LayoutEntry] { &mut self.entries }
+
+    /// Positions all the floaters. The results are in the same order as the
+    /// entries were added.
+    pub fn compute(&mut self, container: impl Into<Boundary>) -> Vec<LayoutResult> {
+        let boundary = container.into();
+        let container = boundary.rect(BoundaryKind::Both);
+
+        // stable sort, so entries with the same priority keep their order
+        let mut order: Vec<usize> = (0..self.entries.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.entries[i].priority));
+
+        let references: Vec<ElemRect> = self.entries.iter().map(|entry| entry.reference).collect();
+        let mut placed: Vec<ElemRect> = Vec::new();
+        let mut results: Vec<Option<LayoutResult>> =
+            (0..self.entries.len()).map(|_| None).collect();
+
+        for i in order {
+            let obstacles: Vec<ElemRect> = placed
+                .iter()
+                .map(|rect| rect.expand(self.gap))
+                .chain(
+                    references
+                        .iter()
+                        .enumerate()
+                        .filter(|&(j, _)| self.avoid_references && j != i)
+                        .map(|(_, reference)| *reference),
+                )
+                .collect();
+            let blocked = |rect: &ElemRect| obstacles.iter().any(|other| rect.overlaps(other));
+
+            let entry = &mut self.entries[i];
+            let (reference, floater) = (entry.reference, entry.floater);
+            let run = |pipeline: &mut Pipeline, placement: Placement| {
+                let opts = pipeline.as_opts().with_placement(placement);
+                compute_position(reference, floater, boundary, opts)
+            };
+
+            // try each placement on a copy, so that stateful modifiers in the
+            // real pipeline only see the placement that is kept
+            let initial = entry.pipeline.placement();
+            let mut trial = entry.pipeline.clone();
+            let mut tried = Vec::new();
+            let mut chosen = None;
+            for placement in entry.fallback_placements() {
+                let info = run(&mut trial, placement);
+                if !blocked(&info.rect) {
+                    chosen = Some((placement, 0.0));
+                    break;
+                }
+                // the pipeline may have moved the floater to another side
+                tried.push((placement, info.side, info.rect));
+            }
+
+            // no placement fits as is: try shifting along the side of the
+            // reference instead
+            if chosen.is_none() {
+                chosen = tried.iter().find_map(|&(placement, side, rect)| {
+                    cross_shift(&rect, side, &reference, &container, &obstacles)
+                        .map(|amount| (placement, amount))
+                });
+            }
+
+            // nothing fits: use the preferred position anyway
+            let (placement, amount) = chosen.unwrap_or((initial, 0.0));
+            let mut info = run(&mut entry.pipeline, placement);
+            if amount != 0.0 {
+                *info.rect.point_mut().coord_cross_mut(info.side) += amount;
+                // keep the arrow pointing at the same place
+                if let Some(arrow) = info.data.get::<ArrowData>().copied() {
+                    info.data.insert(arrow.shifted(-amount));
+                }
+            }
+
+            let result = LayoutResult {
+                collides: blocked(&info.rect),
+                info,
+            };
+            placed.push(result.info.rect);
+            results[i] = Some(result);
+        }
+
+        results.into_iter().flatten().collect()
+    }
+}
+
+/// Finds the smallest amount to move `rect` along the cross axis of `side` so
+/// that it does not overlap any of the `obstacles`.
+///
+/// The moved rect must stay inside the container, and next to the center of
+/// the reference so that it is still attached to it.
+fn cross_shift(
+    rect: &ElemRect,
+    side: Side,
+    reference: &ElemRect,
+    container: &ElemRect,
+    obstacles: &[ElemRect],
+) -> Option<f64> {
+    let start = rect.point().coord_cross(side);
+    let len = rect.size().dim_cross(side);
+
+    // move just past either edge of each obstacle
+    let mut amounts: Vec<f64> = obstacles
+        .iter()
+        .flat_map(|obstacle| {
+            let obstacle_start = obstacle.point().coord_cross(side);
+            let obstacle_end = obstacle_start + obstacle.size().dim_cross(side);
+            [obstacle_end - start, obstacle_start - (start + len)]
+        })
+        .collect();
+    amounts.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
+
+    let container_start = container.point().coord_cross(side);
+    let container_end = container_start + container.size().dim_cross(side);
+    let reference_center = reference.center().coord_cross(side);
+
+    amounts.into_iter().find(|&amount| {
+        let mut moved = *rect;
+        *moved.point_mut().coord_cross_mut(side) += amount;
+        let moved_start = start + amount;
+        let moved_end = moved_start + len;
+
+        moved_start >= container_start
+            && moved_end <= container_end
+            && (moved_start..=moved_end).contains(&reference_center)
+            && !obstacles.iter().any(|other| moved.overlaps(other))
+    })
+}
+
+/// A single floater to position in a [`Layout`].
+#[derive(Debug, Clone)]
+pub struct LayoutEntry {
+    reference: ElemRect,
+    floater: ElemSize,
+    pipeline: Pipeline,
+    priority: i32,
+}
+
+impl LayoutEntry {
+    #[must_use]
+    pub fn new(reference: impl Into<ElemRect>, floater: ElemSize, pipeline: Pipeline) -> Self {
+        Self {
+            reference: reference.into(),
+            floater,
+            pipeline,
+            priority: 0,
+        }
+    }
+
+    /// Entries with a higher priority are placed first, so they are more
+    /// likely to keep their preferred placement. Defaults to 0.
+    #[must_use]
+    pub const fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    #[must_use]
+    pub const fn reference(&self) -> ElemRect { self.reference }
+
+    #[must_use]
+    pub const fn floater(&self) -> ElemSize { self.floater }
+
+    pub const fn pipeline_mut(&mut self) -> &mut Pipeline { &mut self.pipeline }
+
+    /// The placements to try, in order: the placements that the pipeline's
+    /// [`Flip`] checks (or the opposite side and then the adjacent sides if
+    /// there is no flip), each with every alignment.
+    fn fallback_placements(&self) -> Vec<Placement> {
+        let initial = self.pipeline.placement();
+        let candidates = self.pipeline.modifier::<Flip>().map_or_else(
+            || flip().flip_cross(true).candidates(initial),
+            |flip| flip.candidates(initial),
+        );
+        let aligns = [
+            initial.align,
+            Alignment::Center,
+            Alignment::Start,
+            Alignment::End,
+        ];
+
+        let mut placements: Vec<Placement> = Vec::new();
+        for candidate in candidates {
+            for align in aligns {
+                let placement = candidate.with_align(align);
+                if !placements.contains(&placement) {
+                    placements.push(placement);
+                }
+            }
+        }
+        placements
+    }
+}
+
+/// The position of one entry of a [`Layout`].
+#[non_exhaustive]
+pub struct LayoutResult {
+    pub info: PositionInfo,
+    /// Whether the floater still overlaps another floater, because no
+    /// placement could avoid it. Lower priority floaters should usually be
+    /// hidden if this is true.
+    pub collides: bool,
+}
@@ -1,26 +1,86 @@
+pub mod boundary;
+#[cfg(feature = "serde")]
+pub mod config;
+pub mod error;
 pub mod geometry;
+pub mod layout;
 pub mod modifiers;
 pub mod padding;
+pub mod pipeline;
 pub mod space;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod trace;
 #[cfg(feature = "web-utils")]
 pub mod web;
 
-use geometry::{ElemRect, ElemSize, Side, Vec2};
-use modifiers::{Modifier, ModifierState, Modifiers};
+use boundary::{Boundary, BoundaryKind};
+use error::{Input, PositionError};
+use geometry::{Alignment, ElemRect, ElemSize, Placement, Side, Vec2};
+use modifiers::{Modifier, ModifierData, ModifierState, Modifiers, Reset};
+pub use pipeline::Pipeline;
+use trace::{Trace, TraceStep};
 
-#[derive(Debug, Default)]
+/// The default maximum number of times the modifier pipeline can be restarted
+/// with [`StateUpdate::reset`](modifiers::StateUpdate::reset).
+pub const DEFAULT_MAX_RESETS: usize = 50;
+
+#[derive(Debug)]
 pub struct PositionOpts<'a> {
-    side: Side,
+    placement: Placement,
+    max_resets: usize,
+    trace: bool,
     modifiers: Modifiers<'a>,
 }
 
 impl<'a> PositionOpts<'a> {
     #[must_use]
-    pub fn new() -> Self { Self::default() }
+    pub fn new() -> Self {
+        Self {
+            placement: Placement::default(),
+            max_resets: DEFAULT_MAX_RESETS,
+            trace: false,
+            modifiers: Modifiers::new(),
+        }
+    }
 
     #[must_use]
     pub const fn with_side(mut self, side: Side) -> Self {
-        self.side = side;
+        self.placement.side = side;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_align(mut self, align: Alignment) -> Self {
+        self.placement.align = align;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// The maximum number of times a modifier can restart the pipeline. Any
+    /// resets requested after this are ignored.
+    ///
+    /// Defaults to [`DEFAULT_MAX_RESETS`].
+    #[must_use]
+    pub const fn with_max_resets(mut self, max_resets: usize) -> Self {
+        self.max_resets = max_resets;
+        self
+    }
+
+    /// Whether to record a [`Trace`] of every modifier that runs, which is
+    /// returned in [`PositionInfo::trace`].
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub const fn with_trace(mut self, b: bool) -> Self {
+        self.trace = b;
         self
     }
 
@@ -40,62 +100,205 @@ impl<'a> PositionOpts<'a> {
     }
 }
 
+impl Default for PositionOpts<'_> {
+    fn default() -> Self { Self::new() }
+}
+
 #[non_exhaustive]
 pub struct PositionInfo {
     pub rect: ElemRect,
     pub side: Side,
+    pub align: Alignment,
+    /// Extra information reported by the modifiers, such as
+    /// [`ArrowData`](modifiers::arrow::ArrowData).
+    pub data: ModifierData,
+    /// Only recorded if enabled with [`PositionOpts::with_trace`].
+    pub trace: Option<Trace>,
+}
+
+impl PositionInfo {
+    #[must_use]
+    pub const fn placement(&self) -> Placement { Placement::new(self.side, self.align) }
 }
 
-/// Computes the required position of the floater given only its side and no
-/// modifiers.
+/// Computes the required position of the floater given only its placement and
+/// no modifiers.
+///
+/// A [`Side`] can be passed in to center the floater along that side.
 ///
 /// This is intended to only be used by modifiers - use [`compute_position`]
 /// otherwise.
 #[must_use]
-pub fn compute_placement_position(reference: ElemRect, floater: ElemSize, side: Side) -> Vec2 {
-    let x = match side {
-        Side::Top | Side::Bottom => reference.center().x - floater.width() / 2.0,
+pub fn compute_placement_position(
+    reference: ElemRect,
+    floater: ElemSize,
+    placement: impl Into<Placement>,
+) -> Vec2 {
+    let Placement { side, align } = placement.into();
+
+    let mut point = Vec2::new(0.0, 0.0);
+
+    *point.coord_main_mut(side) = match side {
         Side::Left => reference.left() - floater.width(),
+        Side::Top => reference.top() - floater.height(),
         Side::Right => reference.right(),
+        Side::Bottom => reference.bottom(),
     };
 
-    let y = match side {
-        Side::Left | Side::Right => reference.center().y - floater.height() / 2.0,
-        Side::Top => reference.top() - floater.height(),
-        Side::Bottom => reference.bottom(),
+    let reference_start = reference.point().coord_cross(side);
+    let reference_len = reference.size().dim_cross(side);
+    let floater_len = floater.dim_cross(side);
+    *point.coord_cross_mut(side) = match align {
+        Alignment::Start => reference_start,
+        Alignment::Center => reference_start + (reference_len - floater_len) / 2.0,
+        Alignment::End => reference_start + reference_len - floater_len,
     };
 
-    Vec2::new(x, y)
+    point
 }
 
-/// `reference` should be relative to the nearest scrolling context.
+/// `reference` should be relative to the nearest scrolling context. This can
+/// also be a point, or the rect from
+/// [`PointRef::rect`](geometry::PointRef::rect).
 ///
 /// The returned position will also be relative to the same context.
 ///
-/// `container` is the section of the scrolling context that is visible.
+/// `container` is the section of the scrolling context that is visible. This
+/// can also be a [`Boundary`] made of multiple rects.
 #[must_use]
 pub fn compute_position(
-    reference: ElemRect,
+    reference: impl Into<ElemRect>,
     floater: ElemSize,
-    container: ElemRect,
+    container: impl Into<Boundary>,
     opts: PositionOpts,
 ) -> PositionInfo {
-    let point = compute_placement_position(reference, floater, opts.side);
+    let mut reference = reference.into();
+    let boundary = container.into();
+    let mut floater = floater;
+    let mut placement = opts.placement;
+    let mut modifiers = opts.modifiers;
+    let mut resets = 0;
+    let mut data = ModifierData::new();
+    let mut trace = opts.trace.then(Trace::default);
 
-    let mut state = ModifierState::new(
-        reference,
-        ElemRect::from_parts(point, floater),
-        container,
-        opts.side,
-    );
+    let state = 'pipeline: loop {
+        let point = compute_placement_position(reference, floater, placement);
 
-    for modifier in opts.modifiers {
-        let res = modifier.run(&state);
-        state.update_with(&res);
-    }
+        let mut state = ModifierState::new(
+            reference,
+            ElemRect::from_parts(point, floater),
+            boundary,
+            placement,
+        );
+        state.resets = resets;
+        state.data = data;
+        state.tracing = trace.is_some();
+
+        for modifier in modifiers.iter_mut() {
+            let before = trace.is_some().then(|| state.clone());
+            let res = modifier.run(&state);
+            state.update_with(&res);
+            let reset_request = res.reset_request();
+
+            if let (Some(trace), Some(before)) = (&mut trace, before) {
+                trace.push(TraceStep {
+                    name: modifier.name(),
+                    before,
+                    update: res,
+                    after: state.clone(),
+                });
+            }
+
+            if let Some(reset) = reset_request {
+                if resets < opts.max_resets {
+                    resets += 1;
+                    reference = state.reference;
+                    floater = state.floater.size();
+                    placement = match reset {
+                        Reset::Initial => opts.placement,
+                        Reset::Placement(new_placement) => new_placement,
+                    };
+                    data = state.data;
+                    continue 'pipeline;
+                }
+            }
+        }
+
+        break state;
+    };
 
     PositionInfo {
         rect: state.floater,
         side: state.side,
+        align: state.align,
+        data: state.data,
+        trace,
+    }
+}
+
+/// Same as [`compute_position`], but checks that the inputs can be positioned
+/// first.
+///
+/// # Errors
+///
+/// Returns a [`PositionError`] if:
+/// - any position or size is not finite, or any size is negative.
+/// - the container is empty.
+/// - the floater is larger than the container.
+/// - the reference is entirely outside of the container.
+pub fn try_compute_position(
+    reference: impl Into<ElemRect>,
+    floater: ElemSize,
+    container: impl Into<Boundary>,
+    opts: PositionOpts,
+) -> Result<PositionInfo, PositionError> {
+    let reference = reference.into();
+    let boundary = container.into();
+    validate(reference, floater, boundary)?;
+    Ok(compute_position(reference, floater, boundary, opts))
+}
+
+fn validate(
+    reference: ElemRect,
+    floater: ElemSize,
+    boundary: Boundary,
+) -> Result<(), PositionError> {
+    if !reference.is_finite() {
+        return Err(PositionError::NonFinite(Input::Reference));
+    }
+    if !floater.is_finite() {
+        return Err(PositionError::NonFinite(Input::Floater));
+    }
+    let boundary_rects = [Some(boundary.clipping()), boundary.root()];
+    if !boundary_rects.iter().flatten().all(ElemRect::is_finite) {
+        return Err(PositionError::NonFinite(Input::Container));
+    }
+
+    // rects that don't overlap intersect to a negative size, so this has to be
+    // checked first. it also covers any boundary rect with a negative size
+    let container = boundary.rect(BoundaryKind::Both);
+    if container.width() <= 0.0 || container.height() <= 0.0 {
+        return Err(PositionError::EmptyContainer);
+    }
+
+    if reference.size().is_negative() {
+        return Err(PositionError::NegativeSize(Input::Reference));
+    }
+    if floater.is_negative() {
+        return Err(PositionError::NegativeSize(Input::Floater));
     }
+
+    if floater.width() > container.width() || floater.height() > container.height() {
+        return Err(PositionError::FloaterTooLarge);
+    }
+    // touching the edge of the container still counts as inside
+    if reference.right() < container.left()
+        || reference.left() > container.right()
+        || reference.bottom() < container.top()
+        || reference.top() > container.bottom()
+    {
+        return Err(PositionError::ReferenceOutside);
+    }
+
+    Ok(())
 }
@@ -1,23 +1,39 @@
 pub mod offset;
-pub use offset::offset;
+pub use offset::{offset, offset_fn};
 pub mod flip;
 pub use flip::flip;
+pub mod auto_placement;
+pub use auto_placement::auto_placement;
 pub mod shift;
 pub use shift::shift;
 pub mod arrow;
 pub use arrow::arrow;
 pub mod resize;
 pub use resize::resize;
+pub mod hide;
+pub use hide::hide;
+pub mod inline;
+pub use inline::inline;
+pub mod avoid;
+pub use avoid::avoid;
+pub mod round;
+pub use round::round;
 
 // nesting all the modifier stuff into a module so that `modifiers::*` don't
 // have access to private fields
 #[rustfmt::skip] pub use nest::*;
 mod nest {
     use core::fmt;
-    use std::vec;
+    use std::{
+        any::{type_name, Any, TypeId},
+        collections::HashMap,
+        sync::Arc,
+        vec,
+    };
 
     use crate::{
-        geometry::{ElemRect, ElemSize, Vec2},
+        boundary::{Boundary, BoundaryKind},
+        geometry::{Alignment, ElemRect, ElemSize, Placement, Vec2},
         Side,
     };
 
@@ -27,33 +43,60 @@ mod nest {
     /// information to be passed in later. All fields will always be `pub`, so
     /// that you can unpack each field. A `..` pattern must always be included
     /// to ignore unused fields / due to `#[non_exhaustive]`.
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone)]
     #[non_exhaustive]
     pub struct ModifierState {
         pub reference: ElemRect,
         // after initial placement, floater has a position too
         pub floater: ElemRect,
+        /// The area the floater should stay inside of. This is the
+        /// intersection of both parts of the `boundary`.
         pub container: ElemRect,
+        pub boundary: Boundary,
         pub side: Side,
+        pub align: Alignment,
+        /// How many times the pipeline has been restarted by a
+        /// [`StateUpdate::reset`]. Modifiers that request a reset should
+        /// check this to avoid restarting forever.
+        pub resets: usize,
+        /// Data reported by earlier modifiers.
+        pub data: ModifierData,
+        /// Whether a [`Trace`](crate::trace::Trace) is being recorded.
+        /// Modifiers can check this to avoid building
+        /// [`StateUpdate::note`]s that will not be read.
+        pub tracing: bool,
     }
 
     impl ModifierState {
         #[must_use]
-        pub const fn new(
+        pub fn new(
             reference: ElemRect,
             floater: ElemRect,
-            container: ElemRect,
-            side: Side,
+            boundary: impl Into<Boundary>,
+            placement: Placement,
         ) -> Self {
+            let boundary = boundary.into();
             Self {
                 reference,
                 floater,
-                container,
-                side,
+                container: boundary.rect(BoundaryKind::Both),
+                boundary,
+                side: placement.side,
+                align: placement.align,
+                resets: 0,
+                data: ModifierData::new(),
+                tracing: false,
             }
         }
 
+        /// The current side and alignment of the floater.
+        #[must_use]
+        pub const fn placement(&self) -> Placement { Placement::new(self.side, self.align) }
+
         pub fn update_with(&mut self, res: &StateUpdate) {
+            if let Some(reference) = res.reference {
+                self.reference = reference;
+            }
             if let Some(point) = res.point {
                 *self.floater.point_mut() = point;
             }
@@ -63,26 +106,52 @@ mod nest {
             if let Some(side) = res.side {
                 self.side = side;
             }
+            if let Some(align) = res.align {
+                self.align = align;
+            }
+            for entry in &res.data {
+                self.data.insert_entry(entry.clone());
+            }
         }
     }
 
-    #[derive(Default)]
+    #[derive(Default, Clone)]
     pub struct StateUpdate {
+        reference: Option<ElemRect>,
         point: Option<Vec2>,
         size: Option<ElemSize>,
         side: Option<Side>,
+        align: Option<Alignment>,
+        reset: Option<Reset>,
+        data: Vec<DataEntry>,
+        notes: Vec<String>,
     }
 
     impl StateUpdate {
         #[must_use]
         pub const fn new() -> Self {
             Self {
+                reference: None,
                 point: None,
                 size: None,
                 side: None,
+                align: None,
+                reset: None,
+                data: Vec::new(),
+                notes: Vec::new(),
             }
         }
 
+        /// Replaces the reference that later modifiers will position against.
+        ///
+        /// The floater is not moved - this should usually be paired with a new
+        /// floater position.
+        #[must_use]
+        pub const fn reference(mut self, reference: ElemRect) -> Self {
+            self.reference = Some(reference);
+            self
+        }
+
         #[must_use]
         pub const fn point(mut self, point: Vec2) -> Self {
             self.point = Some(point);
@@ -112,16 +181,175 @@ mod nest {
             self
         }
 
+        #[must_use]
+        pub const fn align(mut self, align: Alignment) -> Self {
+            self.align = Some(align);
+            self
+        }
+
+        #[must_use]
+        pub const fn placement(self, placement: Placement) -> Self {
+            self.side(placement.side).align(placement.align)
+        }
+
         #[must_use]
         pub const fn floater(mut self, rect: ElemRect) -> Self {
             self = self.point(rect.point());
             self = self.size(rect.size());
             self
         }
+
+        /// Restarts the pipeline from the first modifier after this update is
+        /// applied, using the initial placement.
+        ///
+        /// Any new reference or floater size in this update is kept, and the
+        /// floater is positioned again from scratch. Later modifiers will not
+        /// run before the restart.
+        #[must_use]
+        pub const fn reset(mut self) -> Self {
+            self.reset = Some(Reset::Initial);
+            self
+        }
+
+        /// Same as [`StateUpdate::reset`], but restarts with a different
+        /// placement.
+        #[must_use]
+        pub const fn reset_to(mut self, placement: Placement) -> Self {
+            self.reset = Some(Reset::Placement(placement));
+            self
+        }
+
+        /// Whether this update will restart the pipeline.
+        #[must_use]
+        pub const fn reset_request(&self) -> Option<Reset> { self.reset }
+
+        /// Reports extra information to later modifiers and the caller of
+        /// [`compute_position`](crate::compute_position).
+        ///
+        /// This replaces any existing data of the same type. Each modifier
+        /// should use its own type for its data.
+        #[must_use]
+        pub fn data<T: Any + Send + Sync>(mut self, value: T) -> Self {
+            self.data.push(DataEntry::new(value));
+            self
+        }
+
+        /// Adds a short explanation of this update to the
+        /// [`Trace`](crate::trace::Trace), if one is being recorded.
+        ///
+        /// Check [`ModifierState::tracing`] before building expensive notes.
+        #[must_use]
+        pub fn note(mut self, note: impl Into<String>) -> Self {
+            self.notes.push(note.into());
+            self
+        }
+
+        /// The notes added with [`StateUpdate::note`].
+        #[must_use]
+        pub fn notes(&self) -> &[String] { &self.notes }
+    }
+
+    impl fmt::Debug for StateUpdate {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // only show the parts that are updated. notes are shown separately
+            // in the trace
+            let mut s = f.debug_struct("StateUpdate");
+            if let Some(reference) = &self.reference {
+                s.field("reference", reference);
+            }
+            if let Some(point) = &self.point {
+                s.field("point", point);
+            }
+            if let Some(size) = &self.size {
+                s.field("size", size);
+            }
+            if let Some(side) = &self.side {
+                s.field("side", side);
+            }
+            if let Some(align) = &self.align {
+                s.field("align", align);
+            }
+            if let Some(reset) = &self.reset {
+                s.field("reset", reset);
+            }
+            if !self.data.is_empty() {
+                s.field("data", &self.data);
+            }
+            s.finish_non_exhaustive()
+        }
+    }
+
+    /// How to restart the pipeline after a [`StateUpdate::reset`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Reset {
+        /// Restart from the placement passed in to
+        /// [`compute_position`](crate::compute_position).
+        Initial,
+        /// Restart from the given placement.
+        Placement(Placement),
+    }
+
+    /// Data reported by modifiers with [`StateUpdate::data`], keyed by type.
+    #[derive(Clone, Default)]
+    pub struct ModifierData(HashMap<TypeId, DataEntry>);
+
+    impl ModifierData {
+        #[must_use]
+        pub fn new() -> Self { Self::default() }
+
+        /// Returns the data of type `T`, if any modifier has reported it.
+        #[must_use]
+        pub fn get<T: Any>(&self) -> Option<&T> {
+            self.0
+                .get(&TypeId::of::<T>())
+                .and_then(|entry| entry.value.downcast_ref())
+        }
+
+        /// Adds some data, replacing any existing data of the same type.
+        ///
+        /// Modifiers should use [`StateUpdate::data`] instead.
+        pub fn insert<T: Any + Send + Sync>(&mut self, value: T) {
+            self.insert_entry(DataEntry::new(value));
+        }
+
+        fn insert_entry(&mut self, entry: DataEntry) { self.0.insert(entry.type_id, entry); }
+    }
+
+    impl fmt::Debug for ModifierData {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_set()
+                .entries(self.0.values().map(|entry| entry.type_name))
+                .finish()
+        }
+    }
+
+    #[derive(Clone)]
+    struct DataEntry {
+        type_id: TypeId,
+        type_name: &'static str,
+        value: Arc<dyn Any + Send + Sync>,
+    }
+
+    impl fmt::Debug for DataEntry {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.type_name) }
+    }
+
+    impl DataEntry {
+        fn new<T: Any + Send + Sync>(value: T) -> Self {
+            Self {
+                type_id: TypeId::of::<T>(),
+                type_name: type_name::<T>(),
+                value: Arc::new(value),
+            }
+        }
     }
 
     pub trait Modifier {
         fn run(&mut self, state: &ModifierState) -> StateUpdate;
+
+        /// The name shown in a [`Trace`](crate::trace::Trace). Defaults to
+        /// the type name.
+        fn name(&self) -> &'static str { type_name::<Self>() }
     }
 
     impl<F> Modifier for F
@@ -138,6 +366,12 @@ mod nest {
         pub fn new() -> Self { Self(Vec::new()) }
 
         pub fn push(&mut self, modifier: &'a mut impl Modifier) { self.0.push(modifier) }
+
+        pub fn push_dyn(&mut self, modifier: &'a mut dyn Modifier) { self.0.push(modifier) }
+
+        pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut (dyn Modifier + 'a)> {
+            self.0.iter_mut().map(|m| &mut **m)
+        }
     }
 
     impl Default for Modifiers<'_> {
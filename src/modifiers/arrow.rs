@@ -5,32 +5,31 @@ use crate::geometry::{side::Axis, Side};
 /// The arrow element should be inside the floater element, where both floater
 /// and arrow has `position: absolute`.
 ///
-/// The `data` parameter is an out parameter, which provides extra information
-/// about the arrow's positioning. This should be a mut reference to a variable
-/// that stores the [`ArrowData`]. No information from this parameter will be
-/// read.
+/// Information about the arrow's positioning is reported as [`ArrowData`],
+/// which can be read from [`PositionInfo::data`](crate::PositionInfo::data)
+/// after calling [`compute_position`](crate::compute_position).
 ///
 /// You should also use the `side` information provided by
 /// [`compute_position`](crate::compute_position) to rotate the arrow as needed.
 /// For the web, you can also use the [`ArrowData::generate_css_text`] method
 /// (requires `web-utils` feature). Extra conditions about the arrow's styles
 /// are required to use it.
-pub fn arrow(inline_len: f64, data: &mut ArrowData) -> Arrow {
+#[must_use]
+pub const fn arrow(inline_len: f64) -> Arrow {
     Arrow {
         inline_len,
-        data,
         padding: 0.0,
     }
 }
 
 #[doc(hidden)]
-pub struct Arrow<'a> {
+#[derive(Clone)]
+pub struct Arrow {
     inline_len: f64,
     padding: f64,
-    data: &'a mut ArrowData,
 }
 
-impl Arrow<'_> {
+impl Arrow {
     /// How far the arrow must stay from the corners of the floater.
     #[must_use]
     pub const fn padding(mut self, padding: f64) -> Self {
@@ -39,7 +38,7 @@ impl Arrow<'_> {
     }
 }
 
-impl<'a> Modifier for Arrow<'a> {
+impl Modifier for Arrow {
     fn run(
         &mut self,
         ModifierState {
@@ -56,7 +55,9 @@ impl<'a> Modifier for Arrow<'a> {
         let shifted_amount =
             floater.center().coord_cross(*side) - reference.center().coord_cross(*side);
 
-        // saturate at 0 in case padding > tooltip size, avoids panic in the clamp
+        // saturate at 0 in case padding > tooltip size. `max` also turns NaN into
+        // 0, so the bounds are never NaN and never crossed, and the clamp can't
+        // panic
         let max_shift = (ideal_center - self.inline_len / 2.0 - self.padding).max(0.0);
         let arrow_shift = shifted_amount.clamp(-max_shift, max_shift);
 
@@ -64,29 +65,24 @@ impl<'a> Modifier for Arrow<'a> {
 
         // !! coordinates are for the top-left arrow element position now
 
-        *self.data = ArrowData {
+        StateUpdate::new().data(ArrowData {
             // move from center to top-left
             offset: skid - self.inline_len / 2.0,
-            center_offset: (ideal_center - skid).abs(),
-        };
-
-        StateUpdate::new()
+            center_offset: skid - ideal_center,
+        })
     }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(into = "ArrowDataRepr"))]
 pub struct ArrowData {
     offset: f64,
+    /// Signed, so that the arrow can be moved by [`ArrowData::shifted`].
     center_offset: f64,
 }
 
 impl ArrowData {
-    /// Creates new [`ArrowData`] with dummy information stored. This should
-    /// not be used before being properly populated with data passed in by the
-    /// [`arrow`] out parameter.
-    #[must_use]
-    pub fn new() -> Self { Self::default() }
-
     /// The offset of the arrow relative to the top-left of the floater element.
     /// This should be used with the floater's side to set the `left` or `top`
     /// CSS property.
@@ -99,7 +95,14 @@ impl ArrowData {
     /// How far the arrow is relative to the ideal position (centered on the
     /// reference element). Will always be non-negative.
     #[must_use]
-    pub const fn center_offset(&self) -> f64 { self.center_offset }
+    pub const fn center_offset(&self) -> f64 { self.center_offset.abs() }
+
+    /// Moves the arrow along the floater by `amount`.
+    pub(crate) const fn shifted(mut self, amount: f64) -> Self {
+        self.offset += amount;
+        self.center_offset += amount;
+        self
+    }
 
     /// Generates CSS properties to set on the arrow element.
     ///
@@ -180,3 +183,21 @@ impl ArrowData {
         ]
     }
 }
+
+/// The serialized form of [`ArrowData`], which uses the public values.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct ArrowDataRepr {
+    offset: f64,
+    center_offset: f64,
+}
+
+#[cfg(feature = "serde")]
+impl From<ArrowData> for ArrowDataRepr {
+    fn from(data: ArrowData) -> Self {
+        Self {
+            offset: data.offset(),
+            center_offset: data.center_offset(),
+        }
+    }
+}
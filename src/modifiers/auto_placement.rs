@@ -0,0 +1,133 @@
+use super::{Modifier, ModifierState, StateUpdate};
+use crate::{
+    boundary::BoundaryKind,
+    compute_placement_position,
+    geometry::{Alignment, ElemRect, Placement, Side},
+    impl_boundary_builder, impl_padding_builder,
+    padding::Padding,
+    space::space_around,
+};
+
+/// Chooses the placement with the most space available, ignoring the initial
+/// side.
+///
+/// This is an alternative to [`flip`](super::flip), which should be used
+/// instead if there is a preferred side. These two modifiers should not be
+/// used together.
+#[must_use]
+pub fn auto_placement() -> AutoPlacement {
+    AutoPlacement {
+        allowed_sides: vec![Side::Top, Side::Bottom, Side::Left, Side::Right],
+        check_cross_axis: false,
+        alignment: Alignment::Center,
+        auto_alignment: true,
+        padding: Padding::default(),
+        boundary: BoundaryKind::default(),
+    }
+}
+
+#[derive(Clone)]
+pub struct AutoPlacement {
+    allowed_sides: Vec<Side>,
+    check_cross_axis: bool,
+    alignment: Alignment,
+    auto_alignment: bool,
+    padding: Padding,
+    boundary: BoundaryKind,
+}
+
+impl AutoPlacement {
+    /// Which sides the floater may be placed on. All four sides are allowed
+    /// by default.
+    ///
+    /// If there are multiple sides with the same amount of space, the earlier
+    /// side is chosen.
+    #[must_use]
+    pub fn allowed_sides(mut self, sides: impl IntoIterator<Item = Side>) -> Self {
+        self.allowed_sides = sides.into_iter().collect();
+        self
+    }
+
+    /// Whether the floater must also fit along the sides of the reference.
+    #[must_use]
+    pub const fn check_cross_axis(mut self, b: bool) -> Self {
+        self.check_cross_axis = b;
+        self
+    }
+
+    /// The preferred alignment of the floater.
+    #[must_use]
+    pub const fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Whether to also try the opposite alignment if the floater does not fit
+    /// with the preferred alignment. Only has an effect if the alignment is
+    /// not [`Alignment::Center`].
+    #[must_use]
+    pub const fn auto_alignment(mut self, b: bool) -> Self {
+        self.auto_alignment = b;
+        self
+    }
+
+    impl_padding_builder!(padding);
+    impl_boundary_builder!(boundary);
+}
+
+impl Modifier for AutoPlacement {
+    fn run(
+        &mut self,
+        ModifierState {
+            reference,
+            floater,
+            boundary,
+            ..
+        }: &ModifierState,
+    ) -> StateUpdate {
+        let container = &boundary.rect(self.boundary);
+        let alignments = if self.auto_alignment && self.alignment != Alignment::Center {
+            vec![self.alignment, self.alignment.opposite()]
+        } else {
+            vec![self.alignment]
+        };
+
+        let candidates = self.allowed_sides.iter().flat_map(|&side| {
+            alignments
+                .iter()
+                .map(move |&align| Placement::new(side, align))
+        });
+
+        // (placement, point, fits, score)
+        // fitting placements are scored by the space left on the main axis,
+        // otherwise by the total amount of overflow (a negative number).
+        let best = candidates
+            .map(|placement| {
+                let side = placement.side;
+                let point = compute_placement_position(*reference, floater.size(), placement);
+                let space = space_around(&ElemRect::from_parts(point, floater.size()), container);
+
+                let main_room = space.on_side(side) - self.padding.outward;
+                let fits = main_room >= 0.0
+                    && (!self.check_cross_axis
+                        || side
+                            .adjacents()
+                            .all(|side| space.on_side(side) >= self.padding.cross));
+
+                let score = if fits {
+                    main_room
+                } else {
+                    space.on_all_sides().filter(|space| *space < 0.0).sum()
+                };
+
+                (placement, point, fits, score)
+            })
+            // keep the earliest candidate on ties
+            .reduce(|best, curr| if (curr.2, curr.3) > (best.2, best.3) { curr } else { best });
+
+        match best {
+            Some((placement, point, ..)) => StateUpdate::new().placement(placement).point(point),
+            None => StateUpdate::new(),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use super::{Modifier, ModifierState, StateUpdate};
+use crate::{
+    boundary::BoundaryKind,
+    compute_placement_position,
+    geometry::{ElemRect, Placement, Side, Vec2},
+    impl_boundary_builder,
+    space::space_around,
+};
+
+/// Moves the floater so that it does not overlap any of the obstacles.
+///
+/// Obstacles are other rects that should stay visible, such as sticky
+/// headers, side panels or the area around a touch point (see
+/// [`ElemRect::expand`]). They must be relative to the same scrolling context
+/// as the reference.
+///
+/// If the floater overlaps an obstacle, it is moved to the closest position
+/// that is clear of all obstacles and stays inside the container. This may
+/// slide the floater past an obstacle, or flip it to another side of the
+/// reference. The floater is never moved on top of the reference.
+///
+/// This should usually be placed after [`flip`](super::flip) and
+/// [`shift`](super::shift), but before [`arrow`](super::arrow).
+pub fn avoid(obstacles: impl IntoIterator<Item = ElemRect>) -> Avoid {
+    Avoid {
+        obstacles: obstacles.into_iter().collect(),
+        gap: 0.0,
+        boundary: BoundaryKind::default(),
+    }
+}
+
+#[derive(Clone)]
+pub struct Avoid {
+    obstacles: Vec<ElemRect>,
+    gap: f64,
+    boundary: BoundaryKind,
+}
+
+impl Avoid {
+    /// The minimum distance to keep between the floater and every obstacle.
+    #[must_use]
+    pub const fn gap(mut self, gap: f64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    impl_boundary_builder!(boundary);
+
+    fn is_blocked(&self, rect: &ElemRect) -> bool {
+        self.obstacles
+            .iter()
+            .any(|obstacle| rect.overlaps(&obstacle.expand(self.gap)))
+    }
+}
+
+impl Modifier for Avoid {
+    fn run(&mut self, state: &ModifierState) -> StateUpdate {
+        let ModifierState {
+            reference,
+            floater,
+            boundary,
+            ..
+        } = state;
+        let container = boundary.rect(self.boundary);
+        let size = floater.size();
+
+        if !self.is_blocked(floater) {
+            return StateUpdate::new();
+        }
+
+        // (point, new placement if flipped)
+        let mut candidates: Vec<(Vec2, Option<Placement>)> = Vec::new();
+
+        // slide along either axis, to just past the edges of each obstacle
+        let obstacles = self.obstacles.iter().map(|o| o.expand(self.gap));
+        let xs: Vec<f64> = obstacles
+            .clone()
+            .flat_map(|o| [o.left() - size.width(), o.right()])
+            .chain([floater.x()])
+            .collect();
+        let ys: Vec<f64> = obstacles
+            .flat_map(|o| [o.top() - size.height(), o.bottom()])
+            .chain([floater.y()])
+            .collect();
+        for &x in &xs {
+            for &y in &ys {
+                candidates.push((Vec2::new(x, y), None));
+            }
+        }
+
+        // other sides of the reference, keeping the same distance from it
+        let current = state.placement();
+        let distance = main_distance(reference, floater, current.side);
+        for side in [Side::Left, Side::Top, Side::Right, Side::Bottom] {
+            let placement = current.with_side(side);
+            let mut point = compute_placement_position(*reference, size, placement);
+            *point.coord_main_mut(side) += match side {
+                Side::Left | Side::Top => -distance,
+                Side::Right | Side::Bottom => distance,
+            };
+            candidates.push((point, Some(placement)));
+        }
+
+        // (point, placement, inside container, distance moved)
+        let valid = candidates.into_iter().filter_map(|(point, placement)| {
+            let rect = ElemRect::from_parts(point, size);
+            if self.is_blocked(&rect) || rect.overlaps(reference) {
+                return None;
+            }
+            let inside = space_around(&rect, &container).min() >= 0.0;
+            let delta = point - floater.point();
+            Some((point, placement, inside, delta.x.hypot(delta.y)))
+        });
+
+        // prefer staying inside the container, but getting out of the way of the
+        // obstacles is more important. keep the earliest candidate on ties.
+        let best = valid
+            .reduce(|best, curr| if (curr.2, -curr.3) > (best.2, -best.3) { curr } else { best });
+
+        match best {
+            Some((point, Some(placement), ..)) => {
+                StateUpdate::new().point(point).placement(placement)
+            }
+            Some((point, None, ..)) => StateUpdate::new().point(point),
+            None => StateUpdate::new(),
+        }
+    }
+}
+
+/// The distance between the floater and the reference, along the main axis of
+/// the side.
+fn main_distance(reference: &ElemRect, floater: &ElemRect, side: Side) -> f64 {
+    match side {
+        Side::Left => reference.left() - floater.right(),
+        Side::Top => reference.top() - floater.bottom(),
+        Side::Right => floater.left() - reference.right(),
+        Side::Bottom => floater.top() - reference.bottom(),
+    }
+}
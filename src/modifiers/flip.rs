@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use super::{Modifier, ModifierState};
 use crate::{
+    boundary::BoundaryKind,
     compute_placement_position,
-    geometry::{ElemRect, Side, Vec2},
-    impl_padding_builder,
+    geometry::{ElemRect, ElemSize, Placement, Side, Vec2},
+    impl_boundary_builder, impl_padding_builder,
     modifiers::StateUpdate,
     padding::Padding,
     space::{space_around, Space},
@@ -16,11 +19,23 @@ pub fn flip() -> Flip {
         check_main_axis: true,
         check_cross_axis: false,
         padding: Padding::default(),
+        boundary: BoundaryKind::default(),
         fallback_method: FallbackMethod::default(),
+        fallback_placements: None,
+        fallback_axis_side_direction: FallbackAxisSideDirection::default(),
+        flip_alignment: false,
+        min_size: None,
+        hysteresis: 0.0,
+        previous: None,
     }
 }
 
+/// Calculates the smallest acceptable size of the floater for a placement.
+pub type MinSizeFn = Arc<dyn Fn(Placement, &ModifierState) -> ElemSize + Send + Sync>;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum FallbackMethod {
     /// Go back to the initial side if none fully fit.
     Initial,
@@ -29,14 +44,38 @@ pub enum FallbackMethod {
     BestFit,
 }
 
-#[allow(clippy::struct_excessive_bools)]
+/// Whether to also try the sides on the other axis, and which one first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum FallbackAxisSideDirection {
+    /// Only try the sides on the other axis if
+    /// [`flip_cross`](Flip::flip_cross) is enabled.
+    #[default]
+    None,
+    /// Try the left or top side first.
+    Start,
+    /// Try the right or bottom side first.
+    End,
+}
+
+#[allow(clippy::struct_excessive_bools, clippy::struct_field_names)]
+#[derive(Clone)]
 pub struct Flip {
     flip_main: bool,
     flip_cross: bool,
     check_main_axis: bool,
     check_cross_axis: bool,
     padding: Padding,
+    boundary: BoundaryKind,
     fallback_method: FallbackMethod,
+    fallback_placements: Option<Vec<Placement>>,
+    fallback_axis_side_direction: FallbackAxisSideDirection,
+    flip_alignment: bool,
+    min_size: Option<MinSizeFn>,
+    hysteresis: f64,
+    /// The placement chosen in the last run, used for hysteresis.
+    previous: Option<Placement>,
 }
 
 impl Flip {
@@ -75,73 +114,277 @@ impl Flip {
         self
     }
 
+    /// The placements to try, in order, if the initial placement does not fit.
+    ///
+    /// This replaces the default order of the opposite side followed by the
+    /// adjacent sides, and ignores [`Flip::flip_main`], [`Flip::flip_cross`]
+    /// and [`Flip::fallback_axis_side_direction`].
+    #[must_use]
+    pub fn fallback_placements<P: Into<Placement>>(
+        mut self,
+        placements: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.fallback_placements = Some(placements.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Whether to try the sides on the other axis after the opposite side,
+    /// and which one to try first.
+    #[must_use]
+    pub const fn fallback_axis_side_direction(mut self, d: FallbackAxisSideDirection) -> Self {
+        self.fallback_axis_side_direction = d;
+        self
+    }
+
+    /// Whether to try the opposite alignment of each placement if the floater
+    /// overflows on the edge it extends towards. For example, a
+    /// [`Alignment::Start`](crate::geometry::Alignment::Start) placement on the
+    /// bottom is tried with
+    /// [`Alignment::End`](crate::geometry::Alignment::End) if it overflows on
+    /// the right.
+    #[must_use]
+    pub const fn flip_alignment(mut self, b: bool) -> Self {
+        self.flip_alignment = b;
+        self
+    }
+
+    /// The smallest size the floater can shrink to, e.g. with a later
+    /// [`resize`](super::resize) modifier.
+    ///
+    /// Each placement is checked as if the floater was shrunk to this size, so
+    /// that a side where the floater fits after resizing is chosen. The floater
+    /// is not resized by this modifier.
+    #[must_use]
+    pub fn min_size(self, size: ElemSize) -> Self { self.min_size_fn(move |_, _| size) }
+
+    /// Same as [`Flip::min_size`], but calculates the minimum size for each
+    /// placement.
+    #[must_use]
+    pub fn min_size_fn(
+        mut self,
+        f: impl Fn(Placement, &ModifierState) -> ElemSize + Send + Sync + 'static,
+    ) -> Self {
+        self.min_size = Some(Arc::new(f));
+        self
+    }
+
+    /// Stops the floater from switching back and forth between placements
+    /// when it is close to the edge of the container, e.g. while scrolling.
+    ///
+    /// The placement chosen in the previous run is kept until it overflows by
+    /// more than `margin`, and an earlier placement is only switched back to
+    /// once it has at least `margin` of extra space.
+    ///
+    /// The same modifier should be reused between runs for this to have an
+    /// effect. Defaults to 0.
+    #[must_use]
+    pub const fn hysteresis(mut self, margin: f64) -> Self {
+        self.hysteresis = margin;
+        self
+    }
+
     impl_padding_builder!(padding);
-}
+    impl_boundary_builder!(boundary);
 
-impl Modifier for Flip {
-    fn run(
+    /// Whether the floater fits with the given amount of space around it.
+    /// `margin` is extra space required on top of the padding.
+    fn fits(&self, placement: Placement, space: &Space, margin: f64) -> bool {
+        let side = placement.side;
+        !((self.check_main_axis && space.on_side(side) < self.padding.outward + margin)
+            || (self.check_cross_axis
+                && side
+                    .adjacents()
+                    .any(|side| space.on_side(side) < self.padding.cross + margin))
+            || (self.flip_alignment
+                && placement.aligned_side().is_some_and(|aligned| {
+                    space.on_side(aligned.opposite()) < self.padding.cross + margin
+                })))
+    }
+
+    /// The placements that are checked, in order and without duplicates,
+    /// starting with `initial`.
+    #[must_use]
+    pub fn candidates(&self, initial: Placement) -> Vec<Placement> {
+        let mut sides = vec![initial];
+        if let Some(placements) = &self.fallback_placements {
+            sides.extend(placements);
+        } else {
+            // the alignment is kept when changing sides
+            if self.flip_main {
+                sides.push(initial.with_side(initial.side.opposite()));
+            }
+            let [start, end] = match initial.side {
+                Side::Top | Side::Bottom => [Side::Left, Side::Right],
+                Side::Left | Side::Right => [Side::Top, Side::Bottom],
+            };
+            let adjacents = match self.fallback_axis_side_direction {
+                FallbackAxisSideDirection::None if self.flip_cross => {
+                    initial.side.adjacents().collect()
+                }
+                FallbackAxisSideDirection::None => vec![],
+                FallbackAxisSideDirection::Start => vec![start, end],
+                FallbackAxisSideDirection::End => vec![end, start],
+            };
+            sides.extend(adjacents.into_iter().map(|side| initial.with_side(side)));
+        }
+
+        let mut candidates: Vec<Placement> = Vec::new();
+        for placement in sides {
+            let flipped = placement.with_align(placement.align.opposite());
+            let placements = if self.flip_alignment && flipped != placement {
+                vec![placement, flipped]
+            } else {
+                vec![placement]
+            };
+            for placement in placements {
+                if !candidates.contains(&placement) {
+                    candidates.push(placement);
+                }
+            }
+        }
+        candidates
+    }
+
+    /// Chooses a placement, adding every placement that did not fit to
+    /// `space_info`.
+    fn choose(
         &mut self,
-        ModifierState {
+        state: &ModifierState,
+        space_info: &mut Vec<(Placement, Space, Vec2, ElemRect)>,
+    ) -> StateUpdate {
+        let ModifierState {
             reference,
             floater,
-            container,
-            side,
+            boundary,
             ..
-        }: &ModifierState,
-    ) -> StateUpdate {
-        let fallbacks = {
-            let mut fallbacks = vec![*side];
-            // TODO: more configurable fallback options
-            self.flip_main.then(|| fallbacks.push(side.opposite()));
-            self.flip_cross.then(|| fallbacks.extend(side.adjacents()));
-            fallbacks
-        };
-
-        let mut space_info: Vec<(Side, Space, Vec2)> = Vec::new();
-
-        for side in fallbacks {
-            let new_pos = compute_placement_position(*reference, floater.size(), side);
-            let new_floater = ElemRect::from_parts(new_pos, floater.size());
-            let space = space_around(&new_floater, container);
-
-            if (self.check_main_axis && space.on_side(side) < self.padding.outward)
-                || (self.check_cross_axis
-                    && side
-                        .adjacents()
-                        .any(|side| space.on_side(side) < self.padding.cross))
-            {
+        } = state;
+        let container = &boundary.rect(self.boundary);
+        let initial = state.placement();
+
+        let candidates = self.candidates(initial);
+        let previous_index = self
+            .previous
+            .and_then(|previous| candidates.iter().position(|p| *p == previous));
+
+        for (i, placement) in candidates.into_iter().enumerate() {
+            let new_pos = compute_placement_position(*reference, floater.size(), placement);
+
+            // check the space as if the floater has shrunk as much as it can
+            let check_size = self.min_size.as_ref().map_or_else(
+                || floater.size(),
+                |f| {
+                    let min_size = f(placement, state);
+                    ElemSize::new(
+                        floater.width().min(min_size.width()),
+                        floater.height().min(min_size.height()),
+                    )
+                },
+            );
+            let check_pos = compute_placement_position(*reference, check_size, placement);
+            let check_rect = ElemRect::from_parts(check_pos, check_size);
+            let space = space_around(&check_rect, container);
+
+            // placements before the previous one must fit by the hysteresis margin,
+            // and the previous placement may overflow by up to the margin
+            let margin = match previous_index {
+                Some(previous) if i < previous => self.hysteresis,
+                Some(previous) if i == previous => -self.hysteresis,
+                _ => 0.0,
+            };
+
+            if !self.fits(placement, &space, margin) {
                 // push in here to avoid unnecessary allocation if the first side works fine
-                space_info.push((side, space, new_pos));
+                space_info.push((placement, space, new_pos, check_rect));
                 continue;
             }
 
-            // enough space: use this side
-            return StateUpdate::new().point(new_pos).side(side);
+            // enough space: use this placement
+            self.previous = Some(placement);
+            return StateUpdate::new()
+                .point(new_pos)
+                .placement(placement)
+                .data(FlipData::new(space_info));
         }
 
+        let data = FlipData::new(space_info);
+
         match self.fallback_method {
-            FallbackMethod::Initial => StateUpdate::new(),
+            FallbackMethod::Initial => {
+                self.previous = Some(initial);
+                StateUpdate::new().data(data)
+            }
             FallbackMethod::BestFit => {
                 // score the best fit by the sides that have the least amount of overflow.
                 // each score should be negative, with the magnitude indicating the total amount
                 // of overflow.
-                let scores = space_info.iter().map(|(_, space, _)| {
+                let scores = space_info.iter().map(|(_, space, _, _)| {
                     space
                         .on_all_sides()
                         .filter(|space| *space < 0.0)
                         .sum::<f64>()
                 });
 
-                let best_fit_index = scores
-                    .enumerate()
-                    .max_by(|a, b| a.1.total_cmp(&b.1))
-                    .expect("should have at least one fallback side")
-                    .0;
+                let Some((best_fit_index, _)) =
+                    scores.enumerate().max_by(|a, b| a.1.total_cmp(&b.1))
+                else {
+                    // no placements were checked
+                    return StateUpdate::new().data(data);
+                };
 
-                let (best_side, _, best_point) = space_info[best_fit_index];
+                let (best_placement, _, best_point, _) = space_info[best_fit_index];
+                self.previous = Some(best_placement);
 
-                StateUpdate::new().side(best_side).point(best_point)
+                StateUpdate::new()
+                    .placement(best_placement)
+                    .point(best_point)
+                    .data(data)
             }
         }
     }
 }
+
+impl Modifier for Flip {
+    fn run(&mut self, state: &ModifierState) -> StateUpdate {
+        let mut space_info = Vec::new();
+        let update = self.choose(state, &mut space_info);
+
+        if !state.tracing {
+            return update;
+        }
+        let notes = space_info.iter().map(|(placement, space, ..)| {
+            let Placement { side, align } = placement;
+            format!("{side:?}/{align:?} does not fit: {space:?}")
+        });
+        notes.fold(update, StateUpdate::note)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FlipData {
+    overflows: Vec<(Placement, Space)>,
+    rects: Vec<ElemRect>,
+}
+
+impl FlipData {
+    fn new(space_info: &[(Placement, Space, Vec2, ElemRect)]) -> Self {
+        Self {
+            overflows: space_info
+                .iter()
+                .map(|(placement, space, _, _)| (*placement, *space))
+                .collect(),
+            rects: space_info.iter().map(|(.., rect)| *rect).collect(),
+        }
+    }
+
+    /// The placements that were checked but did not have enough space, in the
+    /// order they were checked, along with the space around the floater in
+    /// each placement.
+    #[must_use]
+    pub fn overflows(&self) -> &[(Placement, Space)] { &self.overflows }
+
+    /// The rect that was checked for each placement in
+    /// [`FlipData::overflows`], in the same order. This uses the size set by
+    /// [`Flip::min_size`] if it is smaller than the floater.
+    #[must_use]
+    pub fn rects(&self) -> &[ElemRect] { &self.rects }
+}
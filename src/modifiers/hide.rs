@@ -0,0 +1,103 @@
+use super::{Modifier, ModifierState, StateUpdate};
+use crate::{
+    boundary::BoundaryKind,
+    geometry::{ElemRect, Side},
+    impl_boundary_builder,
+    space::{space_around, Space},
+};
+
+/// Detects whether the floater should be hidden.
+///
+/// The result is reported as [`HideData`]. This modifier does not change the
+/// position of the floater.
+///
+/// This should usually be placed after all other modifiers, so that the final
+/// position of the floater is checked.
+#[must_use]
+pub const fn hide() -> Hide {
+    Hide {
+        boundary: BoundaryKind::Both,
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct Hide {
+    boundary: BoundaryKind,
+}
+
+impl Hide {
+    impl_boundary_builder!(boundary);
+}
+
+impl Modifier for Hide {
+    fn run(
+        &mut self,
+        ModifierState {
+            reference,
+            floater,
+            boundary,
+            ..
+        }: &ModifierState,
+    ) -> StateUpdate {
+        let container = &boundary.rect(self.boundary);
+        let reference_offsets = space_around(reference, container);
+        let escaped_offsets = space_around(floater, container);
+
+        // escaped is checked against the clipping boundary only, as that is
+        // what cuts off the reference (e.g. a scrolling ancestor)
+        let clipping = &boundary.clipping();
+        let is_clipped = |rect: &ElemRect| {
+            space_around(rect, clipping)
+                .on_all_sides()
+                .any(|space| space < 0.0)
+        };
+
+        StateUpdate::new().data(HideData {
+            reference_hidden: is_fully_clipped(reference, &reference_offsets),
+            reference_offsets,
+            escaped: is_clipped(reference) && !is_clipped(floater),
+            escaped_offsets,
+        })
+    }
+}
+
+/// Whether the rect is completely outside of the container on any side.
+fn is_fully_clipped(rect: &ElemRect, space: &Space) -> bool {
+    [Side::Left, Side::Top, Side::Right, Side::Bottom]
+        .into_iter()
+        .any(|side| space.on_side(side) <= -rect.size().dim_main(side))
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HideData {
+    reference_hidden: bool,
+    reference_offsets: Space,
+    escaped: bool,
+    escaped_offsets: Space,
+}
+
+impl HideData {
+    /// Whether the reference is completely outside of the container, so the
+    /// floater is not attached to anything visible.
+    #[must_use]
+    pub const fn reference_hidden(&self) -> bool { self.reference_hidden }
+
+    /// The space around the reference inside the container. Negative values
+    /// are the amount that the reference is clipped by on that side.
+    #[must_use]
+    pub const fn reference_offsets(&self) -> Space { self.reference_offsets }
+
+    /// Whether the floater has detached from a clipped reference, i.e. the
+    /// reference is cut off by the clipping boundary but the floater is not.
+    ///
+    /// This happens when the reference is scrolled out of a scrolling
+    /// ancestor, but the floater is still fully visible over other content.
+    #[must_use]
+    pub const fn escaped(&self) -> bool { self.escaped }
+
+    /// The space around the floater inside the container. Negative values are
+    /// the amount that the floater is overflowing by on that side.
+    #[must_use]
+    pub const fn escaped_offsets(&self) -> Space { self.escaped_offsets }
+}
@@ -0,0 +1,112 @@
+use super::{Modifier, ModifierState, StateUpdate};
+use crate::{
+    compute_placement_position,
+    geometry::{ElemRect, Placement, Side, Vec2},
+};
+
+/// Picks one rect of a reference that spans multiple lines to position
+/// against.
+///
+/// `rects` should be the line boxes of the reference, in order, e.g. from
+/// [`Element.getClientRects`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getClientRects).
+/// They must be relative to the same scrolling context as the container.
+///
+/// The chosen rect replaces the reference for all modifiers after this one,
+/// so this should usually be the first modifier. If `rects` is empty, the
+/// reference is left as is.
+pub fn inline(rects: impl IntoIterator<Item = ElemRect>) -> Inline {
+    Inline {
+        rects: rects.into_iter().collect(),
+        pointer: None,
+        method: InlineMethod::default(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InlineMethod {
+    /// Use the first line for [`Side::Top`] and the last line for
+    /// [`Side::Bottom`]. For [`Side::Left`] and [`Side::Right`], use the rect
+    /// closest to the pointer, or the leftmost/rightmost rect if there is no
+    /// pointer.
+    ///
+    /// A rect that contains the pointer is always chosen.
+    #[default]
+    Line,
+    /// Use the bounding box of all the rects.
+    Merged,
+}
+
+#[derive(Clone)]
+pub struct Inline {
+    rects: Vec<ElemRect>,
+    pointer: Option<Vec2>,
+    method: InlineMethod,
+}
+
+impl Inline {
+    /// The position of the pointer (e.g. the mouse or a touch), relative to
+    /// the same scrolling context as the rects.
+    #[must_use]
+    pub const fn pointer(mut self, point: Vec2) -> Self {
+        self.pointer = Some(point);
+        self
+    }
+
+    /// How to choose the rect to position against.
+    #[must_use]
+    pub const fn method(mut self, method: InlineMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Returns the rect that would be used as the reference for the given
+    /// placement.
+    #[must_use]
+    pub fn select(&self, placement: impl Into<Placement>) -> Option<ElemRect> {
+        let (first, rest) = self.rects.split_first()?;
+
+        if self.method == InlineMethod::Merged {
+            return Some(rest.iter().fold(*first, |acc, rect| acc.union(rect)));
+        }
+
+        let closest_to = |point: Vec2| {
+            self.rects
+                .iter()
+                .min_by(|a, b| a.distance_to(point).total_cmp(&b.distance_to(point)))
+        };
+
+        let closest = self.pointer.and_then(closest_to);
+        if let (Some(closest), Some(pointer)) = (closest, self.pointer) {
+            if closest.distance_to(pointer) <= 0.0 {
+                return Some(*closest);
+            }
+        }
+
+        let chosen = match (placement.into().side, closest) {
+            (Side::Top, _) => first,
+            (Side::Bottom, _) => self.rects.last()?,
+            (Side::Left | Side::Right, Some(closest)) => closest,
+            (Side::Left, None) => self
+                .rects
+                .iter()
+                .min_by(|a, b| a.left().total_cmp(&b.left()))?,
+            (Side::Right, None) => self
+                .rects
+                .iter()
+                .max_by(|a, b| a.right().total_cmp(&b.right()))?,
+        };
+
+        Some(*chosen)
+    }
+}
+
+impl Modifier for Inline {
+    fn run(&mut self, state: &ModifierState) -> StateUpdate {
+        let Some(reference) = self.select(state.placement()) else {
+            return StateUpdate::new();
+        };
+
+        let point = compute_placement_position(reference, state.floater.size(), state.placement());
+        StateUpdate::new().reference(reference).point(point)
+    }
+}
@@ -1,17 +1,126 @@
 use super::{Modifier, ModifierState, StateUpdate};
-use crate::geometry::Side;
+use crate::geometry::{Alignment, Side};
 
+/// Moves the floater by a fixed amount.
+///
+/// An `f64` can be passed in to only move the floater away from the reference.
+/// Use [`Offsets`] to also move it along the cross axis.
 #[must_use]
-pub fn offset(amount: f64) -> impl Modifier {
-    move |ModifierState { floater, side, .. }: &_| -> StateUpdate {
-        let pos = floater;
-        let (x, y) = match side {
-            Side::Left => (pos.x() - amount, pos.y()),
-            Side::Right => (pos.x() + amount, pos.y()),
-            Side::Top => (pos.x(), pos.y() - amount),
-            Side::Bottom => (pos.x(), pos.y() + amount),
+pub fn offset(amount: impl Into<Offsets>) -> Offset<Offsets> {
+    Offset {
+        offsets: amount.into(),
+    }
+}
+
+/// Moves the floater by an amount calculated from the current state.
+///
+/// This can be used for offsets based on the size of the reference, or a
+/// different offset for each side.
+#[must_use]
+pub const fn offset_fn<F: FnMut(&ModifierState) -> Offsets>(f: F) -> Offset<F> {
+    Offset { offsets: f }
+}
+
+#[derive(Clone)]
+pub struct Offset<O> {
+    offsets: O,
+}
+
+impl<O: ComputeOffsets> Modifier for Offset<O> {
+    fn run(&mut self, state: &ModifierState) -> StateUpdate {
+        let Offsets {
+            main_axis,
+            cross_axis,
+            alignment_axis,
+        } = self.offsets.offsets(state);
+        let side = state.side;
+
+        // the alignment axis offset replaces the cross axis offset for aligned
+        // placements, and points inwards from the aligned edge.
+        let cross_axis = match (alignment_axis, state.align) {
+            (Some(amount), Alignment::Start) => amount,
+            (Some(amount), Alignment::End) => -amount,
+            _ => cross_axis,
+        };
+
+        let mut point = state.floater.point();
+        *point.coord_main_mut(side) += match side {
+            Side::Left | Side::Top => -main_axis,
+            Side::Right | Side::Bottom => main_axis,
         };
+        *point.coord_cross_mut(side) += cross_axis;
+
+        StateUpdate::new().point(point)
+    }
+}
+
+/// How far to move the floater with the [`offset`] modifier.
+#[allow(clippy::struct_field_names)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct Offsets {
+    /// The distance between the reference and floater. Negative values move
+    /// the floater towards the reference.
+    pub main_axis: f64,
+    /// Moves the floater along the side of the reference. Positive values move
+    /// the floater right or down.
+    pub cross_axis: f64,
+    /// Same as `cross_axis`, but only for placements with a
+    /// [`Alignment::Start`] or [`Alignment::End`] alignment. Positive values
+    /// move the floater inwards from the aligned edge, so the sign is flipped
+    /// for end alignments.
+    ///
+    /// This overrides the cross axis offset when the placement is aligned.
+    pub alignment_axis: Option<f64>,
+}
+
+impl Offsets {
+    /// Only moves the floater away from the reference by `main_axis`.
+    #[must_use]
+    pub const fn new(main_axis: f64) -> Self {
+        Self {
+            main_axis,
+            cross_axis: 0.0,
+            alignment_axis: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_main_axis(mut self, amount: f64) -> Self {
+        self.main_axis = amount;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_cross_axis(mut self, amount: f64) -> Self {
+        self.cross_axis = amount;
+        self
+    }
 
-        StateUpdate::new().point_xy(x, y)
+    #[must_use]
+    pub const fn with_alignment_axis(mut self, amount: f64) -> Self {
+        self.alignment_axis = Some(amount);
+        self
     }
 }
+
+impl From<f64> for Offsets {
+    fn from(main_axis: f64) -> Self { Self::new(main_axis) }
+}
+
+pub trait ComputeOffsets {
+    /// Should return how far to move the floater.
+    fn offsets(&mut self, state: &ModifierState) -> Offsets;
+}
+
+impl ComputeOffsets for Offsets {
+    fn offsets(&mut self, _state: &ModifierState) -> Offsets { *self }
+}
+
+impl<F> ComputeOffsets for F
+where
+    F: FnMut(&ModifierState) -> Offsets,
+{
+    fn offsets(&mut self, state: &ModifierState) -> Offsets { self(state) }
+}
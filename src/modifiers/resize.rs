@@ -1,8 +1,9 @@
 use super::{Modifier, ModifierState, StateUpdate};
 use crate::{
+    boundary::BoundaryKind,
     compute_placement_position,
     geometry::{side::Axis, ElemRect, ElemSize, Side},
-    impl_padding_builder,
+    impl_boundary_builder, impl_padding_builder,
     padding::Padding,
     space::space_around,
 };
@@ -19,17 +20,32 @@ use crate::{
 pub fn resize<F: FnMut(&ElemSize, &ModifierState) -> ElemSize>(recalculator: F) -> Resize<F> {
     Resize {
         padding: Padding::splat(0.0),
+        boundary: BoundaryKind::default(),
+        reset: false,
         recalculator,
     }
 }
 
+#[derive(Clone)]
 pub struct Resize<F> {
     padding: Padding,
+    boundary: BoundaryKind,
+    reset: bool,
     recalculator: F,
 }
 
 impl<F> Resize<F> {
     impl_padding_builder!(padding);
+    impl_boundary_builder!(boundary);
+
+    /// Whether to restart the pipeline if the floater's size changes, so that
+    /// earlier modifiers (e.g. [`flip`](super::flip)) run again with the new
+    /// size.
+    #[must_use]
+    pub const fn reset(mut self, b: bool) -> Self {
+        self.reset = b;
+        self
+    }
 }
 
 impl<F: FnMut(&ElemSize, &ModifierState) -> ElemSize> Modifier for Resize<F> {
@@ -37,9 +53,11 @@ impl<F: FnMut(&ElemSize, &ModifierState) -> ElemSize> Modifier for Resize<F> {
         let ModifierState {
             reference,
             floater,
-            container,
+            boundary,
             side,
+            ..
         } = *state;
+        let container = boundary.rect(self.boundary);
 
         let mut space = space_around(&floater, &container);
         // remove the space from the edge of the reference to the end of the container
@@ -52,6 +70,11 @@ impl<F: FnMut(&ElemSize, &ModifierState) -> ElemSize> Modifier for Resize<F> {
 
         *space.on_side_mut(side.opposite()) -= invalid_space;
 
+        // an aligned floater can only grow away from the edge it is aligned to
+        if let Some(aligned_side) = state.placement().aligned_side() {
+            *space.on_side_mut(aligned_side) = 0.0;
+        }
+
         let (padding_width, padding_height) = match side.axis() {
             Axis::Vertical => (
                 self.padding.cross,
@@ -70,9 +93,31 @@ impl<F: FnMut(&ElemSize, &ModifierState) -> ElemSize> Modifier for Resize<F> {
 
         let new_size = (self.recalculator)(&space, state);
 
-        let new_floater_pos = compute_placement_position(reference, new_size, side);
+        let new_floater_pos = compute_placement_position(reference, new_size, state.placement());
         let new_floater = ElemRect::from_parts(new_floater_pos, new_size);
 
-        StateUpdate::new().floater(new_floater)
+        let size_changed = (new_size.width() - floater.width()).abs() > f64::EPSILON
+            || (new_size.height() - floater.height()).abs() > f64::EPSILON;
+
+        let update = StateUpdate::new()
+            .floater(new_floater)
+            .data(ResizeData { available: space });
+        if self.reset && size_changed {
+            update.reset()
+        } else {
+            update
+        }
     }
 }
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResizeData {
+    available: ElemSize,
+}
+
+impl ResizeData {
+    /// The space that was available to the floater, as passed in to the
+    /// recalculation function of [`resize`].
+    #[must_use]
+    pub const fn available(&self) -> ElemSize { self.available }
+}
@@ -0,0 +1,49 @@
+use super::{arrow::ArrowData, Modifier, ModifierState, StateUpdate};
+use crate::geometry::Vec2;
+
+/// Rounds the position of the floater to the device pixel grid, so that text
+/// in the floater is not blurry.
+///
+/// `scale` is the number of device pixels per unit, e.g. the
+/// `devicePixelRatio` on the web. Nothing is rounded if `scale` is not a
+/// finite, positive number.
+///
+/// This should be the last modifier. If an [`arrow`](super::arrow) modifier
+/// has run, its [`ArrowData`] is also rounded so that the arrow stays in the
+/// same place relative to the floater.
+#[must_use]
+pub const fn round(scale: f64) -> Round { Round { scale } }
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct Round {
+    scale: f64,
+}
+
+impl Round {
+    fn snap(&self, value: f64) -> f64 { (value * self.scale).round() / self.scale }
+}
+
+impl Modifier for Round {
+    fn run(&mut self, state: &ModifierState) -> StateUpdate {
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            return StateUpdate::new();
+        }
+
+        let ModifierState { floater, side, .. } = state;
+        let point = floater.point();
+        let new_point = Vec2::new(self.snap(point.x), self.snap(point.y));
+
+        let update = StateUpdate::new().point(new_point);
+
+        let Some(arrow) = state.data.get::<ArrowData>() else {
+            return update;
+        };
+
+        // round the arrow's absolute position instead of its offset, so that
+        // it is snapped to the same grid as the floater
+        let arrow_pos = point.coord_cross(*side) + arrow.offset();
+        let new_offset = self.snap(arrow_pos) - new_point.coord_cross(*side);
+        update.data(arrow.shifted(new_offset - arrow.offset()))
+    }
+}
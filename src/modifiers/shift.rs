@@ -1,32 +1,88 @@
 use self::limiter::{attached, Attached, ShiftLimiter};
 use super::{Modifier, ModifierState, StateUpdate};
-use crate::{geometry::Side, impl_padding_builder, padding::Padding, space::space_around};
-
-// TODO: option for shifting perpendicular to the side, use with the limiter
-// so that it only shifts away from
+use crate::{
+    boundary::BoundaryKind,
+    geometry::{Side, Vec2},
+    impl_boundary_builder, impl_padding_builder,
+    padding::Padding,
+    space::space_around,
+};
 
 #[allow(clippy::missing_const_for_fn)]
 #[must_use]
 pub fn shift() -> Shift<Attached> {
     Shift {
         padding: Padding::splat(0.0),
+        boundary: BoundaryKind::default(),
+        main_axis: false,
+        cross_axis: true,
+        hysteresis: 0.0,
+        previous: Vec2::new(0.0, 0.0),
         limiter: attached(0.0),
     }
 }
 
+#[derive(Clone)]
 pub struct Shift<L> {
     padding: Padding,
+    boundary: BoundaryKind,
+    main_axis: bool,
+    cross_axis: bool,
+    hysteresis: f64,
+    /// The last non-zero shift amount on each axis, used for hysteresis.
+    previous: Vec2,
     limiter: L,
 }
 
 impl<L> Shift<L> {
     impl_padding_builder!(padding);
+    impl_boundary_builder!(boundary);
+
+    /// Whether to shift the floater towards or away from the reference to keep
+    /// it in view. This may make the floater overlap the reference - use the
+    /// [`away_from_reference`](limiter::away_from_reference) limiter to
+    /// prevent this.
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub const fn main_axis(mut self, b: bool) -> Self {
+        self.main_axis = b;
+        self
+    }
+
+    /// Whether to shift the floater along the side of the reference to keep
+    /// it in view.
+    ///
+    /// Defaults to `true`.
+    #[must_use]
+    pub const fn cross_axis(mut self, b: bool) -> Self {
+        self.cross_axis = b;
+        self
+    }
+
+    /// Ignores shifts of less than `margin` in the opposite direction to the
+    /// previous shift, so that the floater does not jitter back and forth
+    /// when it is close to the edge of the container. While a shift is
+    /// ignored, the floater can overflow the boundary by up to `margin`.
+    ///
+    /// The same modifier should be reused between runs for this to have an
+    /// effect. Defaults to 0.
+    #[must_use]
+    pub const fn hysteresis(mut self, margin: f64) -> Self {
+        self.hysteresis = margin;
+        self
+    }
 
     #[must_use]
     pub fn limiter<U: ShiftLimiter>(self, limiter: U) -> Shift<U> {
         Shift {
             limiter,
             padding: self.padding,
+            boundary: self.boundary,
+            main_axis: self.main_axis,
+            cross_axis: self.cross_axis,
+            hysteresis: self.hysteresis,
+            previous: self.previous,
         }
     }
 }
@@ -35,42 +91,118 @@ impl<L: ShiftLimiter> Modifier for Shift<L> {
     fn run(&mut self, state: &ModifierState) -> StateUpdate {
         let ModifierState {
             floater,
-            container,
+            boundary,
             side,
             ..
         } = state;
-        let space = space_around(floater, container);
-
-        for side in side.adjacents() {
-            let space_on_side = space.on_side(side);
-            if space_on_side < self.padding.cross {
-                let mut new_point = floater.point();
-                let shift_amount = space_on_side - self.padding.cross;
-                *new_point.coord_main_mut(side) += match side {
+        let space = space_around(floater, &boundary.rect(self.boundary));
+
+        // the sides to check on each axis, with the padding required on them
+        let main_sides = [
+            (*side, self.padding.outward),
+            (side.opposite(), self.padding.inward),
+        ];
+        let cross_sides = [
+            (side.anticlockwise(), self.padding.cross),
+            (side.clockwise(), self.padding.cross),
+        ];
+
+        let mut amount = Vec2::new(0.0, 0.0);
+        let mut shifted = false;
+        for (enabled, sides) in [(self.main_axis, main_sides), (self.cross_axis, cross_sides)] {
+            if !enabled {
+                continue;
+            }
+
+            // only shift towards one side on each axis
+            if let Some((side, padding)) = sides
+                .into_iter()
+                .find(|&(side, padding)| space.on_side(side) < padding)
+            {
+                shifted = true;
+                let shift_amount = space.on_side(side) - padding;
+                *amount.coord_main_mut(side) += match side {
                     Side::Top | Side::Left => -shift_amount,
                     Side::Bottom | Side::Right => shift_amount,
                 };
+            }
+        }
+
+        // ignore small reversals of the previous shift direction. an axis that
+        // needs no shift forgets its direction, so that only consecutive runs
+        // are damped
+        for (amount, previous) in [
+            (&mut amount.x, &mut self.previous.x),
+            (&mut amount.y, &mut self.previous.y),
+        ] {
+            if *amount == 0.0 {
+                *previous = 0.0;
+            } else if *amount * *previous < 0.0 && amount.abs() < self.hysteresis {
+                *amount = 0.0;
+            }
+        }
 
-                // run limiter with the new state
-                let mut curr_state = *state;
-                *curr_state.floater.point_mut() = new_point;
-                new_point = self.limiter.reshift(&curr_state);
+        if !shifted {
+            return StateUpdate::new().data(ShiftData::default());
+        }
+
+        // run limiter with the new state. the shift amount is available to the
+        // limiter so that it knows which direction the floater was shifted in.
+        let mut curr_state = state.clone();
+        *curr_state.floater.point_mut() = floater.point() + amount;
+        curr_state.data.insert(ShiftData::new(amount));
+        let new_point = self.limiter.reshift(&curr_state);
+        let amount = new_point - floater.point();
 
-                return StateUpdate::new().point(new_point);
+        // remember the last direction shifted in on each axis
+        for (previous, amount) in [
+            (&mut self.previous.x, amount.x),
+            (&mut self.previous.y, amount.y),
+        ] {
+            if amount.abs() > 0.0 {
+                *previous = amount;
             }
         }
 
         StateUpdate::new()
+            .point(new_point)
+            .data(ShiftData::new(amount))
     }
 }
 
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ShiftData {
+    amount: Vec2,
+}
+
+impl ShiftData {
+    pub(crate) const fn new(amount: Vec2) -> Self { Self { amount } }
+
+    /// How far the floater was moved by the [`shift`] modifier.
+    ///
+    /// When read by a [`ShiftLimiter`], this is the amount before the limiter
+    /// is applied.
+    #[must_use]
+    pub const fn amount(&self) -> Vec2 { self.amount }
+}
+
 pub mod limiter {
-    use super::super::ModifierState;
-    use crate::geometry::{side::Axis, Vec2};
+    use super::{super::ModifierState, ShiftData};
+    use crate::geometry::{ElemRect, Side, Vec2};
 
     pub trait ShiftLimiter {
         /// Should return a new position for where to place
         fn reshift(&mut self, state: &ModifierState) -> Vec2;
+
+        /// Applies another limiter after this one. The second limiter sees the
+        /// floater at the position returned by the first.
+        #[must_use]
+        fn and<U: ShiftLimiter>(self, other: U) -> And<Self, U>
+        where
+            Self: Sized,
+        {
+            And(self, other)
+        }
     }
 
     impl<F> ShiftLimiter for F
@@ -81,6 +213,7 @@ pub mod limiter {
     }
 
     #[doc(hidden)]
+    #[derive(Clone)]
     pub struct NoLimit;
 
     impl ShiftLimiter for NoLimit {
@@ -97,6 +230,7 @@ pub mod limiter {
     }
 
     #[doc(hidden)]
+    #[derive(Clone)]
     pub struct Attached {
         padding: f64,
     }
@@ -111,26 +245,265 @@ pub mod limiter {
                 ..
             }: &ModifierState,
         ) -> Vec2 {
-            let padding = self.padding;
-            match side.axis() {
-                Axis::Horizontal => {
-                    // limit y
-                    let miny = reference.top() + padding - floater.height();
-                    let maxy = reference.bottom() - padding;
-                    let y = floater.y().clamp(miny, maxy);
-                    Vec2::new(floater.x(), y)
-                }
-                Axis::Vertical => {
-                    // limit x
-                    let minx = reference.left() + padding - floater.width();
-                    let maxx = reference.right() - padding;
-                    let x = floater.x().clamp(minx, maxx);
-                    Vec2::new(x, floater.y())
+            let mut point = floater.point();
+
+            // the floater must overlap the reference by at least `padding`
+            let coord = point.coord_cross_mut(*side);
+            *coord = attach(
+                *coord,
+                reference.point().coord_cross(*side),
+                reference.size().dim_cross(*side),
+                floater.size().dim_cross(*side),
+                self.padding,
+            );
+
+            point
+        }
+    }
+
+    #[must_use]
+    pub const fn attached(padding: f64) -> Attached { Attached { padding } }
+
+    #[doc(hidden)]
+    #[derive(Clone)]
+    pub struct AwayFromReference;
+
+    impl ShiftLimiter for AwayFromReference {
+        fn reshift(&mut self, state: &ModifierState) -> Vec2 {
+            let side = state.side;
+            let mut point = state.floater.point();
+            let amount = state
+                .data
+                .get::<ShiftData>()
+                .map_or(0.0, |data| data.amount().coord_main(side));
+
+            // undo any main axis shift towards the reference
+            let towards_reference = match side {
+                Side::Left | Side::Top => amount > 0.0,
+                Side::Right | Side::Bottom => amount < 0.0,
+            };
+            if towards_reference {
+                *point.coord_main_mut(side) -= amount;
+            }
+
+            point
+        }
+    }
+
+    /// Only allows the floater to be shifted away from the reference along the
+    /// main axis, so that it never overlaps the reference. The cross axis is
+    /// not limited.
+    #[must_use]
+    pub const fn away_from_reference() -> AwayFromReference { AwayFromReference }
+
+    #[doc(hidden)]
+    #[derive(Clone)]
+    pub struct LimitShift {
+        offset: f64,
+        main_axis: bool,
+        cross_axis: bool,
+    }
+
+    impl LimitShift {
+        /// How much the floater must still overlap the reference by when it
+        /// is limited, on both axes.
+        #[must_use]
+        pub const fn offset(mut self, offset: f64) -> Self {
+            self.offset = offset;
+            self
+        }
+
+        /// Whether to keep the floater from moving past the reference along
+        /// the main axis, when [`Shift::main_axis`](super::Shift::main_axis)
+        /// is enabled.
+        ///
+        /// This only limits how far the floater is shifted towards the
+        /// reference, so any gap from an earlier
+        /// [`offset`](crate::modifiers::offset()) is kept if no shift is
+        /// needed.
+        ///
+        /// Defaults to `false`.
+        #[must_use]
+        pub const fn main_axis(mut self, b: bool) -> Self {
+            self.main_axis = b;
+            self
+        }
+
+        /// Whether to keep the floater attached to the reference along the
+        /// cross axis.
+        ///
+        /// Defaults to `true`.
+        #[must_use]
+        pub const fn cross_axis(mut self, b: bool) -> Self {
+            self.cross_axis = b;
+            self
+        }
+    }
+
+    impl ShiftLimiter for LimitShift {
+        fn reshift(
+            &mut self,
+            ModifierState {
+                reference,
+                floater,
+                side,
+                data,
+                ..
+            }: &ModifierState,
+        ) -> Vec2 {
+            let mut point = floater.point();
+
+            if self.main_axis {
+                let amount = data
+                    .get::<ShiftData>()
+                    .map_or(0.0, |data| data.amount().coord_main(*side));
+                let coord = point.coord_main_mut(*side);
+                let unshifted = *coord - amount;
+                let reference_start = reference.point().coord_main(*side);
+                let reference_end = reference_start + reference.size().dim_main(*side);
+
+                // only limit a shift towards the reference, and never past
+                // where the floater started
+                match side {
+                    Side::Right | Side::Bottom if amount < 0.0 => {
+                        let min = reference_start + self.offset - floater.size().dim_main(*side);
+                        *coord = coord.max(min.min(unshifted));
+                    }
+                    Side::Left | Side::Top if amount > 0.0 => {
+                        let max = reference_end - self.offset;
+                        *coord = coord.min(max.max(unshifted));
+                    }
+                    _ => {}
                 }
             }
+            if self.cross_axis {
+                let coord = point.coord_cross_mut(*side);
+                *coord = attach(
+                    *coord,
+                    reference.point().coord_cross(*side),
+                    reference.size().dim_cross(*side),
+                    floater.size().dim_cross(*side),
+                    self.offset,
+                );
+            }
+
+            point
         }
     }
 
+    /// Stops the floater from being shifted so far that it detaches from the
+    /// reference. Equivalent to floating-ui's `limitShift`.
+    ///
+    /// By default, this only limits the cross axis, and is the same as
+    /// [`attached`] with the same offset.
     #[must_use]
-    pub const fn attached(padding: f64) -> Attached { Attached { padding } }
+    pub const fn limit_shift() -> LimitShift {
+        LimitShift {
+            offset: 0.0,
+            main_axis: false,
+            cross_axis: true,
+        }
+    }
+
+    #[doc(hidden)]
+    #[derive(Clone)]
+    pub struct Within {
+        rect: ElemRect,
+    }
+
+    impl ShiftLimiter for Within {
+        fn reshift(&mut self, ModifierState { floater, .. }: &ModifierState) -> Vec2 {
+            let (rect, size) = (self.rect, floater.size());
+            Vec2::new(
+                clamp_or_center(floater.x(), rect.left(), rect.right() - size.width()),
+                clamp_or_center(floater.y(), rect.top(), rect.bottom() - size.height()),
+            )
+        }
+    }
+
+    /// Keeps the floater inside of `rect`, such as the track of a range
+    /// slider. The floater is centered in `rect` if it is too large to fit.
+    #[must_use]
+    pub const fn within(rect: ElemRect) -> Within { Within { rect } }
+
+    #[doc(hidden)]
+    #[derive(Clone)]
+    pub struct ArrowRoom {
+        padding: f64,
+    }
+
+    impl ShiftLimiter for ArrowRoom {
+        fn reshift(
+            &mut self,
+            ModifierState {
+                reference,
+                floater,
+                side,
+                ..
+            }: &ModifierState,
+        ) -> Vec2 {
+            let mut point = floater.point();
+
+            // attach to the center of the reference instead of its edges
+            let coord = point.coord_cross_mut(*side);
+            *coord = attach(
+                *coord,
+                reference.center().coord_cross(*side),
+                0.0,
+                floater.size().dim_cross(*side),
+                self.padding,
+            );
+
+            point
+        }
+    }
+
+    /// Keeps enough of the floater next to the center of the reference to fit
+    /// an [`arrow`](crate::modifiers::arrow) pointing at it.
+    ///
+    /// `inline_len` and `padding` should be the same as the values passed to
+    /// the arrow modifier.
+    #[must_use]
+    pub fn arrow_room(inline_len: f64, padding: f64) -> ArrowRoom {
+        ArrowRoom {
+            padding: inline_len / 2.0 + padding,
+        }
+    }
+
+    #[doc(hidden)]
+    #[derive(Clone)]
+    pub struct And<A, B>(A, B);
+
+    impl<A: ShiftLimiter, B: ShiftLimiter> ShiftLimiter for And<A, B> {
+        fn reshift(&mut self, state: &ModifierState) -> Vec2 {
+            let point = self.0.reshift(state);
+            let mut state = state.clone();
+            *state.floater.point_mut() = point;
+            self.1.reshift(&state)
+        }
+    }
+
+    /// Limits the floater's coordinate on one axis so that it overlaps the
+    /// reference by at least `padding`.
+    fn attach(
+        coord: f64,
+        reference_start: f64,
+        reference_len: f64,
+        floater_len: f64,
+        padding: f64,
+    ) -> f64 {
+        let min = reference_start + padding - floater_len;
+        let max = reference_start + reference_len - padding;
+        clamp_or_center(coord, min, max)
+    }
+
+    /// The range is inverted if the floater and reference are too small to fit
+    /// the padding, e.g. for point references. center them instead.
+    fn clamp_or_center(value: f64, min: f64, max: f64) -> f64 {
+        if min <= max {
+            value.clamp(min, max)
+        } else {
+            min.midpoint(max)
+        }
+    }
 }
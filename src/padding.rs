@@ -1,4 +1,8 @@
+/// With the `serde` feature, this deserializes from either a single number for
+/// all parts, or from each part separately (missing parts are 0).
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "PaddingRepr"))]
 pub struct Padding {
     pub outward: f64,
     pub inward: f64,
@@ -17,8 +21,41 @@ impl Padding {
 }
 
 impl From<f64> for Padding {
-    fn from(value: f64) -> Self {
-        Self::splat(value)
+    fn from(value: f64) -> Self { Self::splat(value) }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PaddingRepr {
+    All(f64),
+    Parts(PartsRepr),
+}
+
+#[cfg(feature = "serde")]
+#[derive(Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PartsRepr {
+    outward: f64,
+    inward: f64,
+    cross: f64,
+}
+
+#[cfg(feature = "serde")]
+impl From<PaddingRepr> for Padding {
+    fn from(padding: PaddingRepr) -> Self {
+        match padding {
+            PaddingRepr::All(value) => Self::splat(value),
+            PaddingRepr::Parts(PartsRepr {
+                outward,
+                inward,
+                cross,
+            }) => Self {
+                outward,
+                inward,
+                cross,
+            },
+        }
     }
 }
 
@@ -0,0 +1,158 @@
+use core::fmt;
+use std::any::Any;
+
+use crate::{
+    boundary::Boundary,
+    compute_position,
+    geometry::{Alignment, ElemRect, ElemSize, Placement, Side},
+    modifiers::Modifier,
+    PositionInfo, PositionOpts, DEFAULT_MAX_RESETS,
+};
+
+/// A modifier that can be owned by a [`Pipeline`].
+///
+/// This is implemented for all modifiers that are `Clone + Send + Sync`.
+pub trait SharedModifier: Modifier + Send + Sync {
+    fn clone_boxed(&self) -> Box<dyn SharedModifier>;
+    fn as_modifier(&mut self) -> &mut dyn Modifier;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<M: Modifier + Clone + Send + Sync + 'static> SharedModifier for M {
+    fn clone_boxed(&self) -> Box<dyn SharedModifier> { Box::new(self.clone()) }
+
+    fn as_modifier(&mut self) -> &mut dyn Modifier { self }
+
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+/// An owned version of [`PositionOpts`], which can be built once and reused
+/// for many calls to [`compute_position`].
+///
+/// Pipelines can be cloned and sent across threads. Stateful modifiers keep
+/// their state between calls, and clones will have separate state.
+///
+/// Use [`PositionOpts`] instead to avoid allocating.
+pub struct Pipeline {
+    placement: Placement,
+    max_resets: usize,
+    trace: bool,
+    modifiers: Vec<Box<dyn SharedModifier>>,
+}
+
+impl Pipeline {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            placement: Placement::default(),
+            max_resets: DEFAULT_MAX_RESETS,
+            trace: false,
+            modifiers: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_side(mut self, side: Side) -> Self {
+        self.placement.side = side;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_align(mut self, align: Alignment) -> Self {
+        self.placement.align = align;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// See [`PositionOpts::with_max_resets`].
+    #[must_use]
+    pub const fn with_max_resets(mut self, max_resets: usize) -> Self {
+        self.max_resets = max_resets;
+        self
+    }
+
+    /// See [`PositionOpts::with_trace`].
+    #[must_use]
+    pub const fn with_trace(mut self, b: bool) -> Self {
+        self.trace = b;
+        self
+    }
+
+    /// Can also pass in an Option to only add the modifier if it is [`Some`].
+    #[must_use]
+    pub fn add_modifier<M: SharedModifier + 'static>(
+        mut self,
+        modifier: impl Into<Option<M>>,
+    ) -> Self {
+        if let Some(m) = modifier.into() {
+            self.modifiers.push(Box::new(m));
+        }
+        self
+    }
+
+    #[must_use]
+    pub const fn placement(&self) -> Placement { self.placement }
+
+    /// Returns the first modifier of type `M`, if there is one.
+    #[must_use]
+    pub fn modifier<M: Any>(&self) -> Option<&M> {
+        self.modifiers
+            .iter()
+            .find_map(|modifier| (**modifier).as_any().downcast_ref())
+    }
+
+    /// Borrows the pipeline as [`PositionOpts`].
+    #[must_use]
+    pub fn as_opts(&mut self) -> PositionOpts<'_> {
+        let mut opts = PositionOpts::new()
+            .with_placement(self.placement)
+            .with_max_resets(self.max_resets)
+            .with_trace(self.trace);
+        for modifier in &mut self.modifiers {
+            opts.modifiers.push_dyn(modifier.as_modifier());
+        }
+        opts
+    }
+
+    /// Runs [`compute_position`] with this pipeline.
+    #[must_use]
+    pub fn compute(
+        &mut self,
+        reference: impl Into<ElemRect>,
+        floater: ElemSize,
+        container: impl Into<Boundary>,
+    ) -> PositionInfo {
+        compute_position(reference, floater, container, self.as_opts())
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self { Self::new() }
+}
+
+impl Clone for Pipeline {
+    fn clone(&self) -> Self {
+        Self {
+            placement: self.placement,
+            max_resets: self.max_resets,
+            trace: self.trace,
+            modifiers: self.modifiers.iter().map(|m| m.clone_boxed()).collect(),
+        }
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("placement", &self.placement)
+            .field("max_resets", &self.max_resets)
+            .field("trace", &self.trace)
+            .field("modifiers", &self.modifiers.len())
+            .finish()
+    }
+}
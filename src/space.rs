@@ -1,9 +1,10 @@
 use crate::{geometry::ElemRect, Side};
 
-/// Positive = overflowing by `amount` pixels.
-/// Negative = `amount` pixels left to the boundary.
+/// Positive = `amount` pixels left to the boundary.
+/// Negative = overflowing by `amount` pixels.
 /// 0 = flush with the boundary.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Space {
     pub left: f64,
     pub top: f64,
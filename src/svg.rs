@@ -0,0 +1,285 @@
+//! Renders placement scenarios to SVG, for bug reports and snapshot tests.
+//!
+//! ```
+//! # use floater::{compute_position, geometry::*, modifiers::*, svg::Scene, PositionOpts};
+//! let reference = ElemRect::new(40.0, 80.0, 20.0, 20.0);
+//! let container = ElemRect::new(0.0, 0.0, 200.0, 100.0);
+//! let info = compute_position(
+//!     reference,
+//!     ElemSize::new(50.0, 30.0),
+//!     container,
+//!     PositionOpts::new().add_modifier(&mut flip()),
+//! );
+//!
+//! let svg = Scene::new(reference, container).position(&info).render();
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use core::fmt::Write;
+
+use crate::{
+    boundary::{Boundary, BoundaryKind},
+    geometry::{ElemRect, Placement, Side},
+    modifiers::{arrow::ArrowData, flip::FlipData},
+    padding::Padding,
+    PositionInfo,
+};
+
+/// Space to leave around the drawing.
+const MARGIN: f64 = 10.0;
+
+/// A placement scenario to draw.
+///
+/// Everything except the reference and container is optional.
+#[derive(Debug, Clone)]
+pub struct Scene {
+    reference: ElemRect,
+    boundary: Boundary,
+    padding: Option<Padding>,
+    floater: Option<(ElemRect, Placement)>,
+    candidates: Vec<(ElemRect, Placement)>,
+    arrow: Option<ArrowData>,
+    arrow_len: f64,
+}
+
+impl Scene {
+    #[must_use]
+    pub fn new(reference: impl Into<ElemRect>, container: impl Into<Boundary>) -> Self {
+        Self {
+            reference: reference.into(),
+            boundary: container.into(),
+            padding: None,
+            floater: None,
+            candidates: Vec::new(),
+            arrow: None,
+            arrow_len: 10.0,
+        }
+    }
+
+    /// Draws the result of [`compute_position`](crate::compute_position).
+    ///
+    /// This includes the final floater, every placement that [`flip`] checked
+    /// but did not fit (drawn with the size that was checked), and the arrow
+    /// if an [`arrow`] modifier was used.
+    ///
+    /// [`flip`]: crate::modifiers::flip
+    /// [`arrow`]: crate::modifiers::arrow
+    #[must_use]
+    pub fn position(mut self, info: &PositionInfo) -> Self {
+        self.floater = Some((info.rect, info.placement()));
+        self.candidates = info.data.get::<FlipData>().map_or_else(Vec::new, |data| {
+            data.rects()
+                .iter()
+                .zip(data.overflows())
+                .map(|(rect, (placement, _))| (*rect, *placement))
+                .collect()
+        });
+        self.arrow = info.data.get::<ArrowData>().copied();
+        self
+    }
+
+    /// Draws the padding zones along the inside of the container, as used by
+    /// modifiers like [`flip`](crate::modifiers::flip) and
+    /// [`shift`](crate::modifiers::shift).
+    ///
+    /// The zones are oriented using the side of the floater, or
+    /// [`Side::Bottom`] if [`Scene::position`] has not been called.
+    #[must_use]
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = Some(padding.into());
+        self
+    }
+
+    /// The length of the arrow, which should be the same as the value passed
+    /// to the [`arrow`](crate::modifiers::arrow) modifier. Defaults to 10.
+    #[must_use]
+    pub const fn arrow_len(mut self, len: f64) -> Self {
+        self.arrow_len = len;
+        self
+    }
+
+    /// Renders the scene to an SVG document.
+    ///
+    /// The output is deterministic, so it can be compared against saved
+    /// snapshots.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let container = self.boundary.rect(BoundaryKind::Both);
+        let side = self
+            .floater
+            .map_or(Side::Bottom, |(_, placement)| placement.side);
+
+        // fit everything in the view box
+        let mut bounds = self.reference.union(&self.boundary.clipping());
+        if let Some(root) = self.boundary.root() {
+            bounds = bounds.union(&root);
+        }
+        if let Some((floater, _)) = self.floater {
+            bounds = bounds.union(&floater);
+        }
+        for (rect, _) in &self.candidates {
+            bounds = bounds.union(rect);
+        }
+        let bounds = bounds.expand(MARGIN);
+
+        let mut svg = String::new();
+        _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" font-family="sans-serif" font-size="8">"#,
+            bounds.x(),
+            bounds.y(),
+            bounds.width(),
+            bounds.height(),
+        );
+
+        if let Some(root) = self.boundary.root() {
+            rect(
+                &mut svg,
+                "root",
+                &root,
+                "fill=\"none\" stroke=\"#888\" stroke-dasharray=\"2 2\"",
+            );
+        }
+        rect(
+            &mut svg,
+            "clipping",
+            &self.boundary.clipping(),
+            "fill=\"#f4f4f4\" stroke=\"#888\"",
+        );
+
+        if let Some(padding) = self.padding {
+            for zone in padding_zones(&container, side, padding) {
+                rect(
+                    &mut svg,
+                    "padding",
+                    &zone,
+                    "fill=\"#f80\" fill-opacity=\"0.25\"",
+                );
+            }
+        }
+
+        for (candidate, placement) in &self.candidates {
+            rect(
+                &mut svg,
+                "candidate",
+                candidate,
+                "fill=\"none\" stroke=\"#d33\" stroke-dasharray=\"4 2\"",
+            );
+            label(
+                &mut svg,
+                candidate,
+                &format!("{:?}/{:?}", placement.side, placement.align),
+                "#d33",
+            );
+        }
+
+        rect(
+            &mut svg,
+            "reference",
+            &self.reference,
+            "fill=\"#39f\" fill-opacity=\"0.5\" stroke=\"#39f\"",
+        );
+
+        if let Some((floater, placement)) = self.floater {
+            rect(
+                &mut svg,
+                "floater",
+                &floater,
+                "fill=\"#3b3\" fill-opacity=\"0.5\" stroke=\"#3b3\"",
+            );
+            label(
+                &mut svg,
+                &floater,
+                &format!("{:?}/{:?}", placement.side, placement.align),
+                "#000",
+            );
+
+            if let Some(arrow) = self.arrow {
+                arrow_path(&mut svg, &floater, side, arrow.offset(), self.arrow_len);
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn rect(svg: &mut String, class: &str, rect: &ElemRect, attrs: &str) {
+    _ = writeln!(
+        svg,
+        r#"  <rect class="{class}" x="{}" y="{}" width="{}" height="{}" {attrs}/>"#,
+        rect.x(),
+        rect.y(),
+        rect.width(),
+        rect.height(),
+    );
+}
+
+fn label(svg: &mut String, rect: &ElemRect, text: &str, color: &str) {
+    _ = writeln!(
+        svg,
+        r#"  <text x="{}" y="{}" fill="{color}">{text}</text>"#,
+        rect.x() + 2.0,
+        rect.y() + 9.0,
+    );
+}
+
+/// Draws a triangle on the edge of the floater facing the reference,
+/// pointing towards the reference.
+fn arrow_path(svg: &mut String, floater: &ElemRect, floater_side: Side, offset: f64, len: f64) {
+    let half = len / 2.0;
+    let (start, end, tip) = match floater_side {
+        // arrow is on the bottom edge of the floater
+        Side::Top => {
+            let (x, y) = (floater.x() + offset, floater.bottom());
+            ((x, y), (x + len, y), (x + half, y + half))
+        }
+        Side::Bottom => {
+            let (x, y) = (floater.x() + offset, floater.y());
+            ((x, y), (x + len, y), (x + half, y - half))
+        }
+        Side::Left => {
+            let (x, y) = (floater.right(), floater.y() + offset);
+            ((x, y), (x, y + len), (x + half, y + half))
+        }
+        Side::Right => {
+            let (x, y) = (floater.x(), floater.y() + offset);
+            ((x, y), (x, y + len), (x - half, y + half))
+        }
+    };
+    _ = writeln!(
+        svg,
+        r##"  <path class="arrow" d="M {} {} L {} {} L {} {} Z" fill="#3b3"/>"##,
+        start.0, start.1, tip.0, tip.1, end.0, end.1,
+    );
+}
+
+/// The bands inside the container that the padding keeps the floater out of.
+fn padding_zones(container: &ElemRect, side: Side, padding: Padding) -> [ElemRect; 4] {
+    let zone = |edge: Side, width: f64| {
+        let width = width.max(0.0);
+        match edge {
+            Side::Left => ElemRect::new(container.x(), container.y(), width, container.height()),
+            Side::Right => ElemRect::new(
+                container.right() - width,
+                container.y(),
+                width,
+                container.height(),
+            ),
+            Side::Top => ElemRect::new(container.x(), container.y(), container.width(), width),
+            Side::Bottom => ElemRect::new(
+                container.x(),
+                container.bottom() - width,
+                container.width(),
+                width,
+            ),
+        }
+    };
+
+    [
+        zone(side, padding.outward),
+        zone(side.opposite(), padding.inward),
+        zone(side.anticlockwise(), padding.cross),
+        zone(side.clockwise(), padding.cross),
+    ]
+}
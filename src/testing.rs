@@ -0,0 +1,393 @@
+//! Helpers for testing custom modifiers and limiters.
+//!
+//! ```
+//! use floater::{geometry::*, modifiers::shift, testing::*};
+//!
+//! let scenario = Scenario::new(
+//!     ElemRect::new(0.0, 50.0, 10.0, 10.0),
+//!     ElemSize::new(40.0, 20.0),
+//!     ElemRect::new(0.0, 0.0, 100.0, 100.0),
+//! );
+//! let state = scenario.run(&mut shift());
+//!
+//! assert_inside(&state, &scenario.container());
+//! assert_side(&state, Side::Bottom);
+//! assert_approx_eq(&state.floater, &ElemRect::new(0.0, 60.0, 40.0, 20.0));
+//! ```
+
+use core::fmt;
+
+use crate::{
+    boundary::{Boundary, BoundaryKind},
+    compute_placement_position, compute_position,
+    geometry::{Alignment, ElemRect, ElemSize, Placement, Side, Vec2},
+    modifiers::{
+        shift::{limiter::ShiftLimiter, ShiftData},
+        Modifier, ModifierState, Reset,
+    },
+    space::Space,
+    PositionInfo, PositionOpts, DEFAULT_MAX_RESETS,
+};
+
+/// The default tolerance used by [`assert_approx_eq`].
+pub const TOLERANCE: f64 = 1e-6;
+
+/// A reference, floater and container to position.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    name: String,
+    reference: ElemRect,
+    floater: ElemSize,
+    boundary: Boundary,
+    placement: Placement,
+}
+
+impl Scenario {
+    #[must_use]
+    pub fn new(
+        reference: impl Into<ElemRect>,
+        floater: ElemSize,
+        container: impl Into<Boundary>,
+    ) -> Self {
+        Self {
+            name: String::new(),
+            reference: reference.into(),
+            floater,
+            boundary: container.into(),
+            placement: Placement::default(),
+        }
+    }
+
+    /// A description shown in assertion messages.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_placement(mut self, placement: impl Into<Placement>) -> Self {
+        self.placement = placement.into();
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str { &self.name }
+
+    #[must_use]
+    pub const fn reference(&self) -> ElemRect { self.reference }
+
+    #[must_use]
+    pub const fn floater(&self) -> ElemSize { self.floater }
+
+    #[must_use]
+    pub const fn boundary(&self) -> Boundary { self.boundary }
+
+    /// The area the floater should stay inside of.
+    #[must_use]
+    pub fn container(&self) -> ElemRect { self.boundary.rect(BoundaryKind::Both) }
+
+    #[must_use]
+    pub const fn placement(&self) -> Placement { self.placement }
+
+    /// The state that the first modifier would receive.
+    #[must_use]
+    pub fn state(&self) -> ModifierState {
+        let point = compute_placement_position(self.reference, self.floater, self.placement);
+        ModifierState::new(
+            self.reference,
+            ElemRect::from_parts(point, self.floater),
+            self.boundary,
+            self.placement,
+        )
+    }
+
+    /// Runs a single modifier on the initial state, returning the state after
+    /// its update is applied.
+    ///
+    /// If the modifier requests a reset, it is run again from the new
+    /// placement, like [`compute_position`] does, up to
+    /// [`DEFAULT_MAX_RESETS`] times.
+    #[must_use]
+    pub fn run(&self, modifier: &mut impl Modifier) -> ModifierState {
+        let mut state = self.state();
+        loop {
+            let update = modifier.run(&state);
+            state.update_with(&update);
+
+            let Some(reset) = update.reset_request() else {
+                return state;
+            };
+            if state.resets >= DEFAULT_MAX_RESETS {
+                return state;
+            }
+            let placement = match reset {
+                Reset::Initial => self.placement,
+                Reset::Placement(placement) => placement,
+            };
+            let size = state.floater.size();
+            let point = compute_placement_position(state.reference, size, placement);
+            let mut next = ModifierState::new(
+                state.reference,
+                ElemRect::from_parts(point, size),
+                self.boundary,
+                placement,
+            );
+            next.resets = state.resets + 1;
+            next.data = state.data;
+            state = next;
+        }
+    }
+
+    /// Runs a [`ShiftLimiter`] as if [`shift`](crate::modifiers::shift()) had
+    /// moved the floater by `amount` from its initial position, returning the
+    /// state with the floater at the limited position.
+    ///
+    /// ```
+    /// # use floater::{geometry::*, modifiers::shift::limiter::away_from_reference, testing::*};
+    /// let scenario = Scenario::new(
+    ///     ElemRect::new(0.0, 50.0, 10.0, 10.0),
+    ///     ElemSize::new(40.0, 20.0),
+    ///     ElemRect::new(0.0, 0.0, 100.0, 100.0),
+    /// );
+    /// // a shift up, towards the reference, is undone
+    /// let state = scenario.run_limiter(&mut away_from_reference(), Vec2::new(5.0, -10.0));
+    /// assert_approx_eq(&state.floater, &ElemRect::new(-10.0, 60.0, 40.0, 20.0));
+    /// ```
+    #[must_use]
+    pub fn run_limiter(&self, limiter: &mut impl ShiftLimiter, amount: Vec2) -> ModifierState {
+        let mut state = self.state();
+        let initial = state.floater.point();
+        *state.floater.point_mut() = initial + amount;
+        state.data.insert(ShiftData::new(amount));
+
+        let point = limiter.reshift(&state);
+        *state.floater.point_mut() = point;
+        state.data.insert(ShiftData::new(point - initial));
+        state
+    }
+
+    /// Runs [`compute_position`] with this scenario. The placement of `opts`
+    /// is replaced with the scenario's placement.
+    #[must_use]
+    pub fn compute(&self, opts: PositionOpts) -> PositionInfo {
+        compute_position(
+            self.reference,
+            self.floater,
+            self.boundary,
+            opts.with_placement(self.placement),
+        )
+    }
+}
+
+impl fmt::Display for Scenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.name.is_empty() {
+            write!(f, "{}: ", self.name)?;
+        }
+        write!(
+            f,
+            "reference {:?}, floater {:?}, container {:?}, {:?}",
+            self.reference,
+            self.floater,
+            self.container(),
+            self.placement
+        )
+    }
+}
+
+/// Scenarios at the edges of what modifiers should handle, such as
+/// references in the corners of the container, point references and floaters
+/// that are larger than the container.
+///
+/// Every scenario is repeated for each placement. Modifiers should not panic
+/// or produce non-finite positions for any of these.
+#[must_use]
+pub fn edge_cases() -> Vec<Scenario> {
+    let container = ElemRect::new(0.0, 0.0, 200.0, 100.0);
+    let floater = ElemSize::new(40.0, 20.0);
+    let reference = ElemSize::new(20.0, 10.0);
+
+    let at = |x: f64, y: f64| ElemRect::from_parts(Vec2::new(x, y), reference);
+    let cases = [
+        ("centered", at(90.0, 45.0), floater),
+        ("top left corner", at(0.0, 0.0), floater),
+        ("top right corner", at(180.0, 0.0), floater),
+        ("bottom left corner", at(0.0, 90.0), floater),
+        ("bottom right corner", at(180.0, 90.0), floater),
+        (
+            "point reference",
+            ElemRect::new(100.0, 50.0, 0.0, 0.0),
+            floater,
+        ),
+        (
+            "point reference on edge",
+            ElemRect::new(0.0, 0.0, 0.0, 0.0),
+            floater,
+        ),
+        (
+            "zero sized floater",
+            at(90.0, 45.0),
+            ElemSize::new(0.0, 0.0),
+        ),
+        ("floater fills container", at(90.0, 45.0), container.size()),
+        (
+            "floater wider than container",
+            at(90.0, 45.0),
+            ElemSize::new(300.0, 20.0),
+        ),
+        (
+            "floater taller than container",
+            at(90.0, 45.0),
+            ElemSize::new(40.0, 150.0),
+        ),
+        ("reference fills container", container, floater),
+        ("reference partially outside", at(-10.0, -5.0), floater),
+        ("reference outside", at(300.0, 200.0), floater),
+    ];
+
+    let placements = [Side::Top, Side::Right, Side::Bottom, Side::Left]
+        .into_iter()
+        .flat_map(|side| {
+            [Alignment::Start, Alignment::Center, Alignment::End]
+                .map(|align| Placement::new(side, align))
+        });
+
+    placements
+        .flat_map(|placement| {
+            cases.map(|(name, reference, floater)| {
+                Scenario::new(reference, floater, container)
+                    .with_name(name)
+                    .with_placement(placement)
+            })
+        })
+        .collect()
+}
+
+/// The result of positioning a floater, either from running a modifier or
+/// the whole pipeline.
+pub trait Outcome {
+    fn floater_rect(&self) -> ElemRect;
+    fn placement(&self) -> Placement;
+}
+
+impl Outcome for ModifierState {
+    fn floater_rect(&self) -> ElemRect { self.floater }
+
+    fn placement(&self) -> Placement { Self::placement(self) }
+}
+
+impl Outcome for PositionInfo {
+    fn floater_rect(&self) -> ElemRect { self.rect }
+
+    fn placement(&self) -> Placement { Self::placement(self) }
+}
+
+/// Checks that the floater is entirely inside `container`, allowing for
+/// [`TOLERANCE`].
+///
+/// # Panics
+///
+/// If the floater is not inside the container.
+#[track_caller]
+pub fn assert_inside(outcome: &impl Outcome, container: &ElemRect) {
+    let rect = outcome.floater_rect();
+    let space = crate::space::space_around(&rect, container);
+    assert!(
+        space.on_all_sides().all(|space| space >= -TOLERANCE),
+        "floater {rect:?} is not inside container {container:?} (space: {space:?})",
+    );
+}
+
+/// Checks that the floater does not overlap `rect`, which is usually the
+/// reference. Touching edges are not counted as overlapping.
+///
+/// # Panics
+///
+/// If the floater overlaps the rect.
+#[track_caller]
+pub fn assert_no_overlap(outcome: &impl Outcome, rect: &ElemRect) {
+    let floater = outcome.floater_rect();
+    let overlap = floater.intersect(rect);
+    assert!(
+        overlap.width() <= TOLERANCE || overlap.height() <= TOLERANCE,
+        "floater {floater:?} overlaps {rect:?}",
+    );
+}
+
+/// # Panics
+///
+/// If the floater is not on `side`.
+#[track_caller]
+pub fn assert_side(outcome: &impl Outcome, side: Side) {
+    let actual = outcome.placement().side;
+    assert_eq!(actual, side, "floater is on the wrong side");
+}
+
+/// # Panics
+///
+/// If the floater does not have the same side and alignment as `placement`.
+#[track_caller]
+pub fn assert_placement(outcome: &impl Outcome, placement: impl Into<Placement>) {
+    let actual = outcome.placement();
+    assert_eq!(actual, placement.into(), "floater has the wrong placement");
+}
+
+/// Approximate equality, for types that contain floats.
+pub trait ApproxEq: fmt::Debug {
+    /// Whether every value differs by at most `tolerance`.
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        // also treat equal infinities as equal
+        self == other || (self - other).abs() <= tolerance
+    }
+}
+
+impl ApproxEq for Vec2 {
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.x.approx_eq(&other.x, tolerance) && self.y.approx_eq(&other.y, tolerance)
+    }
+}
+
+impl ApproxEq for ElemSize {
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.as_vec2().approx_eq(other.as_vec2(), tolerance)
+    }
+}
+
+impl ApproxEq for ElemRect {
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.point().approx_eq(&other.point(), tolerance)
+            && self.size().approx_eq(&other.size(), tolerance)
+    }
+}
+
+impl ApproxEq for Space {
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.on_all_sides()
+            .zip(other.on_all_sides())
+            .all(|(a, b)| a.approx_eq(&b, tolerance))
+    }
+}
+
+/// # Panics
+///
+/// If the values differ by more than [`TOLERANCE`].
+#[track_caller]
+pub fn assert_approx_eq<T: ApproxEq>(actual: &T, expected: &T) {
+    assert_approx_eq_within(actual, expected, TOLERANCE);
+}
+
+/// # Panics
+///
+/// If the values differ by more than `tolerance`.
+#[track_caller]
+pub fn assert_approx_eq_within<T: ApproxEq>(actual: &T, expected: &T, tolerance: f64) {
+    assert!(
+        actual.approx_eq(expected, tolerance),
+        "values are not approximately equal (tolerance: {tolerance})\n  actual: {actual:?}\nexpected: {expected:?}",
+    );
+}
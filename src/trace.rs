@@ -0,0 +1,86 @@
+use core::fmt;
+
+use crate::modifiers::{ModifierState, StateUpdate};
+
+/// A record of every modifier that ran in
+/// [`compute_position`](crate::compute_position), in order.
+///
+/// Enable with [`PositionOpts::with_trace`](crate::PositionOpts::with_trace).
+/// The [`Display`](fmt::Display) implementation is intended for bug reports.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    steps: Vec<TraceStep>,
+}
+
+impl Trace {
+    #[must_use]
+    pub fn steps(&self) -> &[TraceStep] { &self.steps }
+
+    pub(crate) fn push(&mut self, step: TraceStep) { self.steps.push(step) }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}. {step}", i + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single run of a modifier.
+#[derive(Debug, Clone)]
+pub struct TraceStep {
+    pub(crate) name: &'static str,
+    pub(crate) before: ModifierState,
+    pub(crate) update: StateUpdate,
+    pub(crate) after: ModifierState,
+}
+
+impl TraceStep {
+    /// The [`Modifier::name`](crate::modifiers::Modifier::name) of the
+    /// modifier.
+    #[must_use]
+    pub const fn name(&self) -> &'static str { self.name }
+
+    #[must_use]
+    pub const fn before(&self) -> &ModifierState { &self.before }
+
+    #[must_use]
+    pub const fn update(&self) -> &StateUpdate { &self.update }
+
+    #[must_use]
+    pub const fn after(&self) -> &ModifierState { &self.after }
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            name,
+            before,
+            update,
+            after,
+        } = self;
+
+        write!(f, "{name}")?;
+        if before.resets > 0 {
+            write!(f, " (after {} resets)", before.resets)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "   before: {:?} {:?}",
+            before.placement(),
+            before.floater
+        )?;
+        writeln!(f, "   update: {update:?}")?;
+        write!(f, "   after:  {:?} {:?}", after.placement(), after.floater)?;
+        for note in update.notes() {
+            write!(f, "\n   note: {note}")?;
+        }
+        Ok(())
+    }
+}
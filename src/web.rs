@@ -14,7 +14,8 @@ impl ElemRect {
     ///
     /// This is intended to be used for the reference element. If the reference
     /// is an inline-level element that could span multiple lines, you should
-    /// probably select one of its rects with [`Element.getClientRects`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getClientRects)
+    /// probably pass its line boxes from [`Element.getClientRects`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getClientRects)
+    /// to the [`inline`](crate::modifiers::inline) modifier.
     ///
     /// See: [`offset_*` MDN documentation](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/offsetLeft)
     #[must_use]
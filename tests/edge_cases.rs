@@ -0,0 +1,35 @@
+//! Runs the modifiers on [`floater::testing::edge_cases`].
+#![cfg(feature = "testing")]
+
+use floater::{
+    modifiers::{arrow, flip, hide, offset, round, shift, shift::limiter::no_limit},
+    testing::{assert_inside, edge_cases},
+    PositionOpts,
+};
+
+#[test]
+fn modifiers_handle_edge_cases() {
+    for scenario in edge_cases() {
+        let info = scenario.compute(
+            PositionOpts::new()
+                .add_modifier(&mut offset(8.0))
+                .add_modifier(&mut flip().flip_cross(true))
+                .add_modifier(&mut shift().main_axis(true).limiter(no_limit()))
+                .add_modifier(&mut arrow(10.0).padding(4.0))
+                .add_modifier(&mut hide())
+                .add_modifier(&mut round(2.0)),
+        );
+        let rect = info.rect;
+        assert!(
+            [rect.x(), rect.y(), rect.width(), rect.height()]
+                .iter()
+                .all(|v| v.is_finite()),
+            "{scenario}"
+        );
+
+        let (floater, container) = (scenario.floater(), scenario.container());
+        if floater.width() <= container.width() && floater.height() <= container.height() {
+            assert_inside(&info, &container);
+        }
+    }
+}
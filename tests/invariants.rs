@@ -0,0 +1,445 @@
+//! Property tests for invariants that every modifier should uphold.
+
+use floater::{
+    compute_placement_position, compute_position,
+    geometry::{Alignment, ElemRect, ElemSize, Placement, Side, Vec2},
+    modifiers::{
+        arrow, arrow::ArrowData, auto_placement, avoid, flip, hide, hide::HideData, inline, offset,
+        offset::Offsets, resize, round, shift, shift::limiter::no_limit,
+    },
+    padding::Padding,
+    space::space_around,
+    PositionOpts,
+};
+use proptest::prelude::*;
+
+const EPSILON: f64 = 1e-6;
+
+fn side() -> impl Strategy<Value = Side> {
+    prop_oneof![
+        Just(Side::Left),
+        Just(Side::Top),
+        Just(Side::Right),
+        Just(Side::Bottom)
+    ]
+}
+
+fn placement() -> impl Strategy<Value = Placement> {
+    let align = prop_oneof![
+        Just(Alignment::Start),
+        Just(Alignment::Center),
+        Just(Alignment::End)
+    ];
+    (side(), align).prop_map(|(side, align)| Placement::new(side, align))
+}
+
+fn size() -> impl Strategy<Value = ElemSize> {
+    (0.0..200.0, 0.0..200.0).prop_map(|(w, h)| ElemSize::new(w, h))
+}
+
+fn rect() -> impl Strategy<Value = ElemRect> {
+    (-100.0..500.0, -100.0..500.0, size())
+        .prop_map(|(x, y, size)| ElemRect::from_parts(Vec2::new(x, y), size))
+}
+
+fn container() -> impl Strategy<Value = ElemRect> {
+    (-50.0..50.0, -50.0..50.0, 100.0..600.0, 100.0..600.0)
+        .prop_map(|(x, y, w, h)| ElemRect::new(x, y, w, h))
+}
+
+/// A reference that is fully inside the container.
+fn scenario() -> impl Strategy<Value = (ElemRect, ElemRect)> {
+    container().prop_flat_map(|container| {
+        let rect = (0.0..1.0, 0.0..1.0, 0.0..1.0, 0.0..1.0).prop_map(
+            move |(x, y, w, h): (f64, f64, f64, f64)| {
+                let width = w * container.width() / 2.0;
+                let height = h * container.height() / 2.0;
+                ElemRect::new(
+                    x.mul_add(container.width() - width, container.x()),
+                    y.mul_add(container.height() - height, container.y()),
+                    width,
+                    height,
+                )
+            },
+        );
+        (rect, Just(container))
+    })
+}
+
+fn padding() -> impl Strategy<Value = Padding> {
+    (0.0..20.0, 0.0..20.0, 0.0..20.0).prop_map(|(outward, inward, cross)| Padding {
+        outward,
+        inward,
+        cross,
+    })
+}
+
+/// Any `f64`, including NaN, infinities and negative values.
+fn any_f64() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        4 => -1000.0..1000.0,
+        1 => Just(f64::NAN),
+        1 => Just(f64::INFINITY),
+        1 => Just(f64::NEG_INFINITY),
+        1 => Just(0.0),
+    ]
+}
+
+fn any_rect() -> impl Strategy<Value = ElemRect> {
+    (any_f64(), any_f64(), any_f64(), any_f64()).prop_map(|(x, y, w, h)| ElemRect::new(x, y, w, h))
+}
+
+/// Sum of the overflow on every side. Always non-positive.
+fn overflow(rect: &ElemRect, container: &ElemRect) -> f64 {
+    space_around(rect, container)
+        .on_all_sides()
+        .filter(|space| *space < 0.0)
+        .sum()
+}
+
+/// The distance from the edge of the reference to the edge of the container
+/// on `side`.
+fn room_on_side(reference: &ElemRect, container: &ElemRect, side: Side) -> f64 {
+    match side {
+        Side::Left => reference.left() - container.left(),
+        Side::Top => reference.top() - container.top(),
+        Side::Right => container.right() - reference.right(),
+        Side::Bottom => container.bottom() - reference.bottom(),
+    }
+}
+
+fn is_finite(rect: &ElemRect) -> bool {
+    [rect.x(), rect.y(), rect.width(), rect.height()]
+        .iter()
+        .all(|v| v.is_finite())
+}
+
+proptest! {
+    #[test]
+    fn placement_is_flush_with_reference(reference in rect(), floater in size(), placement in placement()) {
+        let point = compute_placement_position(reference, floater, placement);
+        let rect = ElemRect::from_parts(point, floater);
+
+        let (near, far) = match placement.side {
+            Side::Left => (rect.right(), reference.left()),
+            Side::Top => (rect.bottom(), reference.top()),
+            Side::Right => (rect.left(), reference.right()),
+            Side::Bottom => (rect.top(), reference.bottom()),
+        };
+        prop_assert!((near - far).abs() < EPSILON);
+
+        let side = placement.side;
+        let (rect_start, reference_start) = (point.coord_cross(side), reference.point().coord_cross(side));
+        let (rect_len, reference_len) = (floater.dim_cross(side), reference.size().dim_cross(side));
+        let diff = match placement.align {
+            Alignment::Start => rect_start - reference_start,
+            Alignment::Center => (rect_start + rect_len / 2.0) - (reference_start + reference_len / 2.0),
+            Alignment::End => (rect_start + rect_len) - (reference_start + reference_len),
+        };
+        prop_assert!(diff.abs() < EPSILON);
+    }
+
+    #[test]
+    fn space_adds_up_to_container(rect in rect(), container in container()) {
+        let space = space_around(&rect, &container);
+        prop_assert!((space.left + rect.width() + space.right - container.width()).abs() < EPSILON);
+        prop_assert!((space.top + rect.height() + space.bottom - container.height()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn shift_keeps_floater_inside(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        padding in padding(),
+    ) {
+        let side = placement.side;
+        prop_assume!(floater.dim_cross(side) + 2.0 * padding.cross <= container.size().dim_cross(side));
+
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut shift().padding(padding).limiter(no_limit())),
+        );
+
+        let space = space_around(&info.rect, &container);
+        for side in side.adjacents() {
+            prop_assert!(space.on_side(side) >= padding.cross - EPSILON);
+        }
+    }
+
+    #[test]
+    fn flip_best_fit_is_no_worse_than_initial(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        padding in padding(),
+    ) {
+        let initial = ElemRect::from_parts(compute_placement_position(reference, floater, placement), floater);
+
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut flip().padding(padding)),
+        );
+
+        prop_assert!(overflow(&info.rect, &container) >= overflow(&initial, &container) - EPSILON);
+    }
+
+    #[test]
+    fn arrow_stays_within_padding(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        len in 0.0..30.0,
+        padding in 0.0..20.0,
+    ) {
+        let side = placement.side;
+        let floater_len = floater.dim_cross(side);
+        prop_assume!(floater_len >= 2.0f64.mul_add(padding, len));
+
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut shift())
+                .add_modifier(&mut arrow(len).padding(padding)),
+        );
+
+        let data = info.data.get::<ArrowData>().expect("arrow should report data");
+        prop_assert!(data.offset() >= padding - EPSILON);
+        prop_assert!(data.offset() <= floater_len - padding - len + EPSILON);
+    }
+
+    #[test]
+    fn modifiers_produce_finite_positions(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        padding in padding(),
+        obstacles in prop::collection::vec(rect(), 0..4),
+        scale in 0.5..4.0,
+    ) {
+        let lines = [reference, ElemRect::new(reference.x(), reference.bottom(), reference.width() / 2.0, reference.height())];
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut inline(lines))
+                .add_modifier(&mut offset(8.0))
+                .add_modifier(&mut auto_placement().padding(padding))
+                .add_modifier(&mut flip().padding(padding).flip_cross(true))
+                .add_modifier(&mut resize(|available, state| {
+                    ElemSize::new(
+                        available.width().clamp(0.0, state.floater.width()),
+                        available.height().clamp(0.0, state.floater.height()),
+                    )
+                }).padding(padding))
+                .add_modifier(&mut shift().padding(padding).main_axis(true))
+                .add_modifier(&mut avoid(obstacles))
+                .add_modifier(&mut arrow(10.0).padding(4.0))
+                .add_modifier(&mut hide())
+                .add_modifier(&mut round(scale)),
+        );
+
+        prop_assert!(is_finite(&info.rect));
+        if let Some(data) = info.data.get::<ArrowData>() {
+            prop_assert!(data.offset().is_finite());
+        }
+    }
+
+    #[test]
+    fn modifiers_do_not_panic_on_bad_input(
+        reference in any_rect(),
+        floater in (any_f64(), any_f64()).prop_map(|(w, h)| ElemSize::new(w, h)),
+        container in any_rect(),
+        placement in placement(),
+        padding in (any_f64(), any_f64(), any_f64()).prop_map(|(outward, inward, cross)| Padding { outward, inward, cross }),
+    ) {
+        let _info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut offset(padding.outward))
+                .add_modifier(&mut auto_placement().padding(padding))
+                .add_modifier(&mut flip().padding(padding))
+                .add_modifier(&mut resize(|available, _| *available).padding(padding))
+                .add_modifier(&mut shift().padding(padding).main_axis(true))
+                .add_modifier(&mut avoid([container]))
+                .add_modifier(&mut arrow(padding.cross).padding(padding.inward))
+                .add_modifier(&mut hide())
+                .add_modifier(&mut round(2.0)),
+        );
+    }
+
+    #[test]
+    fn try_compute_position_accepts_valid_input((reference, container) in scenario(), floater in size()) {
+        prop_assume!(floater.width() <= container.width() && floater.height() <= container.height());
+        let info = floater::try_compute_position(reference, floater, container, PositionOpts::new());
+        prop_assert!(info.is_ok());
+    }
+
+    #[test]
+    fn avoid_clears_obstacles(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        obstacles in prop::collection::vec(rect(), 1..4),
+    ) {
+        // sliding along the side of the reference to just before the leftmost
+        // or topmost obstacle is always free, so there is a spot to move to
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut avoid(obstacles.clone())),
+        );
+
+        for obstacle in &obstacles {
+            prop_assert!(!info.rect.overlaps(obstacle), "{:?} overlaps {obstacle:?}", info.rect);
+        }
+    }
+
+    #[test]
+    fn round_snaps_to_device_pixels(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        scale in 0.5..4.0,
+    ) {
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut round(scale)),
+        );
+
+        for value in [info.rect.x(), info.rect.y()] {
+            let device = value * scale;
+            prop_assert!((device - device.round()).abs() < EPSILON, "{value} * {scale} is not whole");
+        }
+    }
+
+    #[test]
+    fn offset_moves_by_configured_amounts(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        main in -20.0..20.0,
+        cross in -20.0..20.0,
+    ) {
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut offset(Offsets::new(main).with_cross_axis(cross))),
+        );
+
+        let side = placement.side;
+        let distance = match side {
+            Side::Left => reference.left() - info.rect.right(),
+            Side::Top => reference.top() - info.rect.bottom(),
+            Side::Right => info.rect.left() - reference.right(),
+            Side::Bottom => info.rect.top() - reference.bottom(),
+        };
+        prop_assert!((distance - main).abs() < EPSILON);
+
+        let initial = compute_placement_position(reference, floater, placement);
+        let moved = info.rect.point().coord_cross(side) - initial.coord_cross(side);
+        prop_assert!((moved - cross).abs() < EPSILON);
+    }
+
+    #[test]
+    fn resize_fits_available_space(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+        padding in padding(),
+    ) {
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut resize(|available, state| {
+                    ElemSize::new(
+                        available.width().min(state.floater.width()).max(0.0),
+                        available.height().min(state.floater.height()).max(0.0),
+                    )
+                }).padding(padding)),
+        );
+
+        let side = placement.side;
+        let main_room = room_on_side(&reference, &container, side) - padding.outward - padding.inward;
+        let cross_room = container.size().dim_cross(side) - padding.cross;
+        let size = info.rect.size();
+        prop_assert!(size.dim_main(side) <= main_room.max(0.0) + EPSILON);
+        prop_assert!(size.dim_cross(side) <= cross_room.max(0.0) + EPSILON);
+    }
+
+    #[test]
+    fn hide_reports_reference_outside_container(
+        reference in rect(),
+        container in container(),
+        floater in size(),
+        placement in placement(),
+    ) {
+        prop_assume!(reference.width() > 0.0 && reference.height() > 0.0);
+
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut hide()),
+        );
+
+        let data = info.data.get::<HideData>().expect("hide should report data");
+        prop_assert_eq!(data.reference_hidden(), !reference.overlaps(&container));
+    }
+
+    #[test]
+    fn auto_placement_chooses_side_with_most_space(
+        (reference, container) in scenario(),
+        floater in size(),
+        placement in placement(),
+    ) {
+        let room = |side: Side| room_on_side(&reference, &container, side) - floater.dim_main(side);
+        let most = [Side::Left, Side::Top, Side::Right, Side::Bottom]
+            .map(room)
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max);
+        prop_assume!(most >= 0.0);
+
+        let info = compute_position(
+            reference,
+            floater,
+            container,
+            PositionOpts::new()
+                .with_placement(placement)
+                .add_modifier(&mut auto_placement()),
+        );
+
+        prop_assert!(room(info.side) >= most - EPSILON);
+    }
+}
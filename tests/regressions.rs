@@ -0,0 +1,228 @@
+//! Regression tests for specific sequences of inputs.
+
+use floater::{
+    boundary::Boundary,
+    compute_position,
+    error::PositionError,
+    geometry::{ElemRect, ElemSize, Placement, Side},
+    layout::{Layout, LayoutEntry},
+    modifiers::{
+        arrow,
+        arrow::ArrowData,
+        flip, hide,
+        hide::HideData,
+        offset, round, shift,
+        shift::limiter::{limit_shift, no_limit},
+        Modifier, ModifierState, StateUpdate,
+    },
+    try_compute_position, Pipeline, PositionOpts,
+};
+
+#[test]
+fn reset_restarts_from_initial_placement() {
+    // reset_to(Left), then reset() should go back to Top, not stay on Left
+    let mut restarts = |state: &ModifierState| match state.resets {
+        0 => StateUpdate::new().reset_to(Placement::from(Side::Left)),
+        1 => StateUpdate::new().reset(),
+        _ => StateUpdate::new(),
+    };
+    let info = compute_position(
+        ElemRect::new(100.0, 100.0, 20.0, 20.0),
+        ElemSize::new(40.0, 20.0),
+        ElemRect::new(0.0, 0.0, 300.0, 300.0),
+        PositionOpts::new()
+            .with_side(Side::Top)
+            .add_modifier(&mut restarts),
+    );
+    assert_eq!(info.side, Side::Top);
+}
+
+/// Reports how many times it has run.
+#[derive(Clone, Default)]
+struct CountRuns(usize);
+
+impl Modifier for CountRuns {
+    fn run(&mut self, _state: &ModifierState) -> StateUpdate {
+        self.0 += 1;
+        StateUpdate::new().data(Self(self.0))
+    }
+}
+
+#[test]
+fn layout_only_keeps_state_of_chosen_placement() {
+    let reference = ElemRect::new(100.0, 100.0, 10.0, 10.0);
+    let floater = ElemSize::new(40.0, 20.0);
+    let container = ElemRect::new(0.0, 0.0, 300.0, 300.0);
+    let pipeline = Pipeline::new().add_modifier(CountRuns::default());
+
+    // the second floater has to try every alignment on the bottom before
+    // moving to the top
+    let mut layout = Layout::new()
+        .add_entry(LayoutEntry::new(reference, floater, pipeline.clone()))
+        .add_entry(LayoutEntry::new(reference, floater, pipeline));
+    let results = layout.compute(container);
+    assert_eq!(results[1].info.side, Side::Top);
+
+    for entry in layout.entries_mut() {
+        let info = entry.pipeline_mut().compute(reference, floater, container);
+        assert_eq!(info.data.get::<CountRuns>().map(|runs| runs.0), Some(2));
+    }
+}
+
+#[test]
+fn limit_shift_main_axis_keeps_offset_gap() {
+    let reference = ElemRect::new(100.0, 100.0, 20.0, 20.0);
+    let container = ElemRect::new(0.0, 0.0, 300.0, 300.0);
+
+    for gap in [10.0, 5.0] {
+        let info = compute_position(
+            reference,
+            ElemSize::new(40.0, 20.0),
+            container,
+            PositionOpts::new()
+                .add_modifier(&mut offset(gap))
+                .add_modifier(&mut shift().limiter(limit_shift().main_axis(true))),
+        );
+        assert_eq!(info.rect.y(), 120.0 + gap);
+    }
+}
+
+#[test]
+fn limit_shift_main_axis_stops_at_reference() {
+    // the floater has to be shifted up past the top of the reference to fit
+    let info = compute_position(
+        ElemRect::new(100.0, 100.0, 20.0, 20.0),
+        ElemSize::new(40.0, 80.0),
+        ElemRect::new(0.0, 0.0, 300.0, 100.0),
+        PositionOpts::new().add_modifier(
+            &mut shift()
+                .main_axis(true)
+                .limiter(limit_shift().main_axis(true).offset(5.0)),
+        ),
+    );
+    assert_eq!(info.rect.y(), 25.0);
+}
+
+#[test]
+fn shift_hysteresis_only_damps_consecutive_runs() {
+    let container = ElemRect::new(0.0, 0.0, 300.0, 300.0);
+    let floater = ElemSize::new(40.0, 20.0);
+    let mut shift = shift().hysteresis(10.0).limiter(no_limit());
+    let mut run = |reference_x: f64| {
+        compute_position(
+            ElemRect::new(reference_x, 100.0, 20.0, 20.0),
+            floater,
+            container,
+            PositionOpts::new().add_modifier(&mut shift),
+        )
+        .rect
+        .x()
+    };
+
+    // shifted right, then no shift, then overflowing the right edge by 5
+    assert_eq!(run(-20.0), 0.0);
+    assert_eq!(run(150.0), 140.0);
+    assert_eq!(run(275.0), 260.0);
+}
+
+#[test]
+fn round_ignores_invalid_scale() {
+    for scale in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+        let info = compute_position(
+            ElemRect::new(100.3, 100.0, 20.0, 20.0),
+            ElemSize::new(40.0, 20.0),
+            ElemRect::new(0.0, 0.0, 300.0, 300.0),
+            PositionOpts::new()
+                .add_modifier(&mut arrow(10.0))
+                .add_modifier(&mut round(scale)),
+        );
+        assert_eq!(info.rect.x(), 90.3);
+        let arrow = info.data.get::<ArrowData>().unwrap();
+        assert_eq!(arrow.offset(), 15.0);
+    }
+}
+
+#[test]
+fn hide_escaped_when_clipped_reference_detaches() {
+    // a scrolling container inside the viewport
+    let boundary = Boundary::new(ElemRect::new(0.0, 0.0, 200.0, 100.0))
+        .with_root(ElemRect::new(0.0, 0.0, 400.0, 400.0));
+    let mut escaped = |reference: ElemRect| {
+        let info = compute_position(
+            reference,
+            ElemSize::new(40.0, 20.0),
+            boundary,
+            PositionOpts::new()
+                .with_side(Side::Top)
+                .add_modifier(&mut hide()),
+        );
+        info.data.get::<HideData>().unwrap().escaped()
+    };
+
+    assert!(!escaped(ElemRect::new(100.0, 50.0, 20.0, 20.0)));
+    // half scrolled out of the bottom, but the floater above is visible
+    assert!(escaped(ElemRect::new(100.0, 90.0, 20.0, 20.0)));
+    // only the floater is cut off
+    assert!(!escaped(ElemRect::new(100.0, 10.0, 20.0, 20.0)));
+}
+
+#[test]
+fn layout_uses_entry_flip_and_shifts_when_no_placement_fits() {
+    let floater = ElemSize::new(60.0, 20.0);
+    let container = ElemRect::new(0.0, 0.0, 300.0, 300.0);
+    // this flip only checks the initial side, so the layout must not try the top
+    let pipeline = Pipeline::new().add_modifier(flip().flip_main(false));
+
+    let mut layout = Layout::new()
+        .add_entry(LayoutEntry::new(
+            ElemRect::new(100.0, 100.0, 10.0, 10.0),
+            floater,
+            pipeline.clone(),
+        ))
+        .add_entry(LayoutEntry::new(
+            ElemRect::new(130.0, 100.0, 10.0, 10.0),
+            floater,
+            pipeline,
+        ));
+    let results = layout.compute(container);
+
+    // every alignment on the bottom overlaps the first floater at 75..135
+    assert_eq!(results[0].info.rect.x(), 75.0);
+    assert_eq!(results[1].info.side, Side::Bottom);
+    assert_eq!(results[1].info.rect.x(), 135.0);
+    assert!(!results[1].collides);
+}
+
+#[test]
+fn disjoint_boundary_is_empty_container() {
+    let boundary = Boundary::from_rects([
+        ElemRect::new(0.0, 0.0, 100.0, 100.0),
+        ElemRect::new(200.0, 0.0, 100.0, 100.0),
+    ])
+    .unwrap();
+    let result = try_compute_position(
+        ElemRect::new(50.0, 50.0, 10.0, 10.0),
+        ElemSize::new(20.0, 20.0),
+        boundary,
+        PositionOpts::new(),
+    );
+    assert!(matches!(result, Err(PositionError::EmptyContainer)));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn scenario_run_applies_resets() {
+    let scenario = floater::testing::Scenario::new(
+        ElemRect::new(100.0, 100.0, 20.0, 20.0),
+        ElemSize::new(40.0, 20.0),
+        ElemRect::new(0.0, 0.0, 300.0, 300.0),
+    );
+    let mut flips_left = |state: &ModifierState| match state.resets {
+        0 => StateUpdate::new().reset_to(Placement::from(Side::Left)),
+        _ => StateUpdate::new(),
+    };
+    let state = scenario.run(&mut flips_left);
+    assert_eq!(state.side, Side::Left);
+    assert_eq!(state.resets, 1);
+    assert_eq!(state.floater.x(), 60.0);
+}
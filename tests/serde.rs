@@ -0,0 +1,56 @@
+//! Tests for the serialized forms of the `serde` feature.
+#![cfg(feature = "serde")]
+
+use floater::{
+    compute_position,
+    config::PipelineConfig,
+    geometry::{ElemRect, ElemSize},
+    modifiers::{arrow, arrow::ArrowData, flip, offset, shift},
+    PositionOpts,
+};
+
+#[test]
+fn arrow_data_serializes_public_values() {
+    // the floater is shifted right by 20 to stay inside the container, so the
+    // arrow sits 20 left of its center. the center offset is serialized as
+    // the public distance, which is never negative
+    let info = compute_position(
+        ElemRect::new(5.0, 270.0, 20.0, 20.0),
+        ElemSize::new(60.0, 40.0),
+        ElemRect::new(0.0, 0.0, 300.0, 300.0),
+        PositionOpts::new()
+            .add_modifier(&mut flip().padding(5.0))
+            .add_modifier(&mut shift().padding(5.0))
+            .add_modifier(&mut offset(5.0))
+            .add_modifier(&mut arrow(10.0).padding(4.0)),
+    );
+    let arrow = info.data.get::<ArrowData>().unwrap();
+    assert_eq!(
+        serde_json::to_string(arrow).unwrap(),
+        r#"{"offset":5.0,"center_offset":20.0}"#
+    );
+}
+
+#[test]
+fn pipeline_config_rejects_unknown_fields() {
+    let parse = serde_json::from_str::<PipelineConfig>;
+    assert!(
+        parse(r#"{ "side": "top", "modifiers": [{ "type": "offset", "main_axis": 4 }] }"#).is_ok()
+    );
+    assert!(parse(r#"{ "sdie": "top" }"#).is_err());
+    assert!(parse(r#"{ "modifers": [] }"#).is_err());
+    assert!(parse(r#"{ "modifiers": [{ "type": "offset", "main_axs": 4 }] }"#).is_err());
+    assert!(parse(r#"{ "modifiers": [{ "type": "flip", "paddin": 4 }] }"#).is_err());
+    assert!(parse(r#"{ "modifiers": [{ "type": "round", "scale": 2, "scael": 3 }] }"#).is_err());
+    assert!(parse(r#"{ "modifiers": [{ "type": "hide", "boundary": "root" }] }"#).is_ok());
+    assert!(parse(r#"{ "modifiers": [{ "type": "hide", "boundry": "root" }] }"#).is_err());
+    assert!(parse(
+        r#"{ "modifiers": [{ "type": "flip", "fallback_placements": [{ "side": "top", "algn": "start" }] }] }"#
+    )
+    .is_err());
+    assert!(parse(
+        r#"{ "modifiers": [{ "type": "shift", "limiter": { "limit_shift": { "ofset": 5 } } }] }"#
+    )
+    .is_err());
+    assert!(parse(r#"{ "modifiers": [{ "type": "flip", "padding": { "outwrd": 5 } }] }"#).is_err());
+}
@@ -0,0 +1,52 @@
+//! Snapshot tests for the SVG renderer. Run with `UPDATE_SNAPSHOTS=1` to
+//! write the expected output after an intended change.
+#![cfg(feature = "svg")]
+
+use std::{env, fs, path::PathBuf};
+
+use floater::{
+    compute_position,
+    geometry::{ElemRect, ElemSize, Side},
+    modifiers::{arrow, flip, offset, shift},
+    svg::Scene,
+    PositionOpts,
+};
+
+fn assert_snapshot(name: &str, svg: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots", name]
+        .iter()
+        .collect();
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, svg).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()));
+    assert_eq!(svg, expected, "snapshot {name} does not match");
+}
+
+#[test]
+fn flip_with_min_size() {
+    // the full floater fits on neither side. the top is too small even at the
+    // minimum size, so flip moves to the bottom
+    let reference = ElemRect::new(60.0, 30.0, 20.0, 20.0);
+    let container = ElemRect::new(0.0, 0.0, 200.0, 150.0);
+    let info = compute_position(
+        reference,
+        ElemSize::new(60.0, 120.0),
+        container,
+        PositionOpts::new()
+            .with_side(Side::Top)
+            .add_modifier(&mut flip().min_size(ElemSize::new(60.0, 40.0)).padding(5.0))
+            .add_modifier(&mut shift().padding(5.0))
+            .add_modifier(&mut offset(4.0))
+            .add_modifier(&mut arrow(10.0)),
+    );
+    assert_eq!(info.side, Side::Bottom);
+
+    let svg = Scene::new(reference, container)
+        .position(&info)
+        .padding(5.0)
+        .render();
+    assert_snapshot("flip_with_min_size.svg", &svg);
+}
@@ -2,8 +2,10 @@
 
 use floater::{
     compute_position,
-    geometry::{ElemRect, ElemSize, Side},
-    modifiers::{arrow, arrow::ArrowData, flip, offset, resize, shift, shift::limiter},
+    geometry::{Alignment, ElemRect, ElemSize, Placement, Side},
+    modifiers::{
+        arrow, arrow::ArrowData, flip, hide, hide::HideData, offset, resize, shift, shift::limiter,
+    },
     PositionOpts,
 };
 use leptos::*;
@@ -91,7 +93,6 @@ fn Single() -> impl IntoView {
             logging::log!("con == {container:?}");
 
             let do_flip = true;
-            let mut arrow_data = ArrowData::new();
 
             let data = compute_position(
                 ref_rect,
@@ -116,9 +117,8 @@ fn Single() -> impl IntoView {
                             .limiter(limiter::attached(20.0)),
                     )
                     .add_modifier(&mut offset(15.0))
-                    .add_modifier(
-                        &mut arrow(arrow_el.offset_width() as f64, &mut arrow_data).padding(5.0),
-                    ),
+                    .add_modifier(&mut arrow(arrow_el.offset_width() as f64).padding(5.0))
+                    .add_modifier(&mut hide()),
             );
             let (x, y) = data.rect.xy();
             let side = data.side;
@@ -127,8 +127,12 @@ fn Single() -> impl IntoView {
             let tip_styles = (*floater).style();
             tip_styles.set_property("top", &format!("{y}px")).ok()?;
             tip_styles.set_property("left", &format!("{x}px")).ok()?;
+            let hide_data = data.data.get::<HideData>()?;
+            let visibility = if hide_data.reference_hidden() { "hidden" } else { "visible" };
+            tip_styles.set_property("visibility", visibility).ok()?;
 
-            arrow_data
+            data.data
+                .get::<ArrowData>()?
                 .generate_css_props(side, arrow_el.offset_width() as f64, "px")
                 .into_iter()
                 .for_each(|(k, v)| {
@@ -200,14 +204,19 @@ fn Dropdown() -> impl IntoView {
             let min_height = 100.0;
             let tip_size = ElemSize::new(50.0, min_height);
 
-            let mut arrow_data = ArrowData::new();
-
             let data = compute_position(
                 ref_rect,
                 tip_size,
                 container,
                 PositionOpts::new()
-                    .with_side(Side::Bottom)
+                    .with_placement(Placement::new(Side::Bottom, Alignment::Start))
+                    // choose the side where the dropdown can be at least `min_height` tall
+                    .add_modifier(
+                        &mut flip()
+                            .min_size(ElemSize::new(0.0, min_height))
+                            .padding_outward(20.0)
+                            .padding_cross(5.0),
+                    )
                     .add_modifier(
                         &mut resize(|available, state| {
                             logging::warn!("{state:?}");
@@ -222,7 +231,6 @@ fn Dropdown() -> impl IntoView {
                         .padding_cross(5.0)
                         .padding_inward(15.0),
                     )
-                    .add_modifier(&mut flip().padding_outward(20.0).padding_cross(5.0))
                     .add_modifier(
                         &mut shift()
                             .padding_outward(20.0)
@@ -231,9 +239,7 @@ fn Dropdown() -> impl IntoView {
                             .limiter(limiter::attached(20.0)),
                     )
                     .add_modifier(&mut offset(15.0))
-                    .add_modifier(
-                        &mut arrow(arrow_el.offset_width() as f64, &mut arrow_data).padding(5.0),
-                    ),
+                    .add_modifier(&mut arrow(arrow_el.offset_width() as f64).padding(5.0)),
             );
             let (x, y) = data.rect.xy();
             let side = data.side;
@@ -243,7 +249,8 @@ fn Dropdown() -> impl IntoView {
             tip_styles.set_property("top", &format!("{y}px")).ok()?;
             tip_styles.set_property("left", &format!("{x}px")).ok()?;
 
-            arrow_data
+            data.data
+                .get::<ArrowData>()?
                 .generate_css_props(side, arrow_el.offset_width() as f64, "px")
                 .into_iter()
                 .for_each(|(k, v)| {
@@ -314,8 +321,6 @@ fn Diamond(s: Side) -> impl IntoView {
                 floater.offset_height() as f64,
             );
 
-            let mut arrow_data = ArrowData::new();
-
             let data = compute_position(
                 ref_rect,
                 tip_size,
@@ -330,7 +335,7 @@ fn Diamond(s: Side) -> impl IntoView {
                             .limiter(limiter::attached(20.0)),
                     )
                     .add_modifier(&mut offset(15.0))
-                    .add_modifier(&mut arrow(arrow_size.width(), &mut arrow_data).padding(5.0)),
+                    .add_modifier(&mut arrow(arrow_size.width()).padding(5.0)),
             );
             let (x, y) = data.rect.xy();
             let side = data.side;
@@ -340,7 +345,8 @@ fn Diamond(s: Side) -> impl IntoView {
             tip_styles.set_property("top", &format!("{y}px")).ok()?;
             tip_styles.set_property("left", &format!("{x}px")).ok()?;
 
-            arrow_data
+            data.data
+                .get::<ArrowData>()?
                 .generate_css_props(side, arrow_el.offset_width() as f64, "px")
                 .into_iter()
                 .for_each(|(k, v)| {